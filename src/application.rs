@@ -0,0 +1,215 @@
+//! Application holds the application layer structures, as exported by mod-sflow, memcached and
+//! other instrumented services. Transactions are sampled as flow records while the per
+//! application totals arrive as counter records.
+
+// Std Lib Imports
+use std::net;
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+   pub method: u32,       /* 0 = OTHER, 1 = OPTIONS, 2 = GET, 3 = HEAD, 4 = POST, 5 = PUT,
+                             6 = DELETE, 7 = TRACE, 8 = CONNECT */
+   pub protocol: u32,     /* HTTP protocol version, major * 1000 + minor (e.g. HTTP1.1 = 1001) */
+   pub uri: String,       /* URI exactly as it came from the client */
+   pub host: String,      /* Host value from request header */
+   pub referer: String,   /* Referer value from request header */
+   pub useragent: String, /* User-Agent value from request header */
+   pub xff: String,       /* X-Forwarded-For value from request header */
+   pub authuser: String,  /* RFC 1413 identity of user */
+   pub mime_type: String, /* Mime-Type of response */
+   pub req_bytes: u64,    /* Content-Length of request */
+   pub resp_bytes: u64,   /* Content-Length of response */
+   pub duration_us: u32,  /* duration of the operation (in microseconds) */
+   pub status: i32,       /* HTTP status code */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct HttpCounters {
+   pub method_option_count: u32,
+   pub method_get_count: u32,
+   pub method_head_count: u32,
+   pub method_post_count: u32,
+   pub method_put_count: u32,
+   pub method_delete_count: u32,
+   pub method_trace_count: u32,
+   pub method_connect_count: u32,
+   pub method_other_count: u32,
+   pub status_1xx_count: u32,
+   pub status_2xx_count: u32,
+   pub status_3xx_count: u32,
+   pub status_4xx_count: u32,
+   pub status_5xx_count: u32,
+   pub status_other_count: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct MemcacheOperation {
+   pub protocol: u32,    /* 0 = OTHER, 1 = ASCII, 2 = BINARY */
+   pub cmd: u32,         /* 0 = OTHER, 1 = SET, 2 = ADD, 3 = REPLACE, 4 = APPEND, 5 = PREPEND,
+                            6 = CAS, 7 = GET, 8 = GETS, 9 = INCR, 10 = DECR, 11 = DELETE,
+                            12 = STATS, 13 = FLUSH, 14 = VERSION, 15 = QUIT, 16 = TOUCH */
+   pub key: String,      /* key used to store/retrieve data */
+   pub nkeys: u32,       /* number of keys (including sampled key) */
+   pub value_bytes: u32, /* size of the value (in bytes) */
+   pub duration_us: u32, /* duration of the operation (in microseconds) */
+   pub status: u32,      /* 0 = UNKNOWN, 1 = OK, 2 = ERROR, 3 = CLIENT_ERROR, 4 = SERVER_ERROR,
+                            5 = STORED, 6 = NOT_STORED, 7 = EXISTS, 8 = NOT_FOUND,
+                            9 = DELETED */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct MemcacheCounters {
+   pub cmd_set: u32,
+   pub cmd_touch: u32,
+   pub cmd_flush: u32,
+   pub get_hits: u32,
+   pub get_misses: u32,
+   pub delete_hits: u32,
+   pub delete_misses: u32,
+   pub incr_hits: u32,
+   pub incr_misses: u32,
+   pub decr_hits: u32,
+   pub decr_misses: u32,
+   pub cas_hits: u32,
+   pub cas_misses: u32,
+   pub cas_badval: u32,
+   pub auth_cmds: u32,
+   pub auth_errors: u32,
+   pub threads: u32,
+   pub conn_yields: u32,
+   pub listen_disabled_num: u32,
+   pub curr_connections: u32,
+   pub rejected_connections: u32,
+   pub total_connections: u32,
+   pub connection_structures: u32,
+   pub evictions: u32,
+   pub reclaimed: u32,
+   pub curr_items: u32,
+   pub total_items: u32,
+   pub bytes_read: u64,
+   pub bytes_written: u64,
+   pub bytes: u64,
+   pub limit_maxbytes: u64,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct AppContext {
+   pub application: String, /* application name, e.g. "payment" */
+   pub operation: String,   /* operation name, e.g. "get.customer.name" */
+   pub attributes: String,  /* operation specific attributes, encoded as a URL query string */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct AppOperation {
+   pub context: AppContext,  /* attributes describing the operation */
+   pub status_descr: String, /* additional text describing status (e.g. "unknown client") */
+   pub req_bytes: u64,       /* size of request body (exclude headers) */
+   pub resp_bytes: u64,      /* size of response body (exclude headers) */
+   pub duration_us: u32,     /* duration of the operation (microseconds) */
+   pub status: u32,          /* 0 = SUCCESS, 1 = OTHER, 2 = TIMEOUT, 3 = INTERNAL_ERROR,
+                                4 = BAD_REQUEST, 5 = FORBIDDEN, 6 = TOO_LARGE,
+                                7 = NOT_IMPLEMENTED, 8 = NOT_FOUND, 9 = UNAVAILABLE,
+                                10 = UNAUTHORIZED */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct AppParentContext {
+   pub context: AppContext, /* the operation that initiated this one */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct AppInitiator {
+   pub actor: String, /* business level identifier of the requester, e.g. customer id */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct AppTarget {
+   pub actor: String, /* business level identifier of the target, e.g. customer id */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct AppOperations {
+   pub application: String,
+   pub success: u32,
+   pub other: u32,
+   pub timeout: u32,
+   pub internal_error: u32,
+   pub bad_request: u32,
+   pub forbidden: u32,
+   pub too_large: u32,
+   pub not_implemented: u32,
+   pub not_found: u32,
+   pub unavailable: u32,
+   pub unauthorized: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct AppResources {
+   pub user_time: u32,   /* user time (ms) */
+   pub system_time: u32, /* system time (ms) */
+   pub mem_used: u64,    /* memory used in bytes */
+   pub mem_max: u64,     /* max memory in bytes */
+   pub fd_open: u32,     /* number of open file descriptors */
+   pub fd_max: u32,      /* max number of file descriptors */
+   pub conn_open: u32,   /* number of open network connections */
+   pub conn_max: u32,    /* max number of network connections */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct AppWorkers {
+   pub workers_active: u32, /* number of active workers */
+   pub workers_idle: u32,   /* number of idle workers */
+   pub workers_max: u32,    /* max number of workers */
+   pub req_delayed: u32,    /* requests delayed waiting for a worker */
+   pub req_dropped: u32,    /* requests dropped because no worker was available */
+}
+}
+
+// The socket records give the connection that served an application transaction, so it can be
+// tied back to the network flow. The proxy variants reuse the same layout to describe the
+// connection a proxy made on to the next server.
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedSocketIpv4 {
+   pub protocol: u32,           /* IP Protocol type (for example, TCP = 6, UDP = 17) */
+   pub local_ip: net::Ipv4Addr,  /* local IP address */
+   pub remote_ip: net::Ipv4Addr, /* remote IP address */
+   pub local_port: u32,         /* TCP/UDP local port number or equivalent */
+   pub remote_port: u32,        /* TCP/UDP remote port number or equivalent */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedSocketIpv6 {
+   pub protocol: u32,           /* IP Protocol type (for example, TCP = 6, UDP = 17) */
+   pub local_ip: net::Ipv6Addr,  /* local IP address */
+   pub remote_ip: net::Ipv6Addr, /* remote IP address */
+   pub local_port: u32,         /* TCP/UDP local port number or equivalent */
+   pub remote_port: u32,        /* TCP/UDP remote port number or equivalent */
+}
+}
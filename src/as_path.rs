@@ -0,0 +1,29 @@
+//! AS path segments as carried in the extended gateway record. A path is made of segments that
+//! are either an ordered AS_SEQUENCE or an unordered AS_SET (RFC 4271).
+
+add_code_enum!{
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AsPathSegmentKind {
+    AsSet = 1,
+    AsSequence = 2,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct AsPathSegment {
+    pub kind: AsPathSegmentKind,
+    pub asns: Vec<u32>,
+}
+}
+
+impl AsPathSegment {
+    /// path_length is how much this segment adds to the AS path length when comparing routes. An
+    /// AS_SET counts as a single hop no matter how many ASNs it holds.
+    pub fn path_length(&self) -> usize {
+        match self.kind {
+            AsPathSegmentKind::AsSet => 1,
+            _ => self.asns.len(),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Broadcom holds the structures exported by switches built on Broadcom ASICs, enterprise 4413.
+//! They cover buffer utilization from the Buffer Statistics Tracking (BST) feature and ASIC
+//! forwarding table usage.
+
+/// BROADCOM is Broadcom's SMI Private Enterprise Number.
+pub const BROADCOM: u32 = 4413;
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct BstEgressQueue {
+   pub queue: u32, /* egress queue number selected for sampled packet */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct BstDeviceBuffers {
+   pub uc_pc: i32, /* unicast buffers percentage utilization, in 100ths of a percent */
+   pub mc_pc: i32, /* multicast buffers percentage utilization, in 100ths of a percent */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct BstPortBuffers {
+   pub ingress_uc_pc: i32,           /* ingress unicast buffers utilization */
+   pub ingress_mc_pc: i32,           /* ingress multicast buffers utilization */
+   pub egress_uc_pc: i32,            /* egress unicast buffers utilization */
+   pub egress_mc_pc: i32,            /* egress multicast buffers utilization */
+   pub egress_queue_uc_pc: Vec<i32>, /* per egress queue unicast buffers utilization, at most 8 */
+   pub egress_queue_mc_pc: Vec<i32>, /* per egress queue multicast buffers utilization, at most 8 */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct BcmTables {
+   pub host_entries: u32,
+   pub host_entries_max: u32,
+   pub ipv4_entries: u32,
+   pub ipv4_entries_max: u32,
+   pub ipv6_entries: u32,
+   pub ipv6_entries_max: u32,
+   pub ipv4_ipv6_entries: u32,
+   pub ipv4_ipv6_entries_max: u32,
+   pub long_ipv6_entries: u32,
+   pub long_ipv6_entries_max: u32,
+   pub total_routes: u32,
+   pub total_routes_max: u32,
+   pub ecmp_nexthops: u32,
+   pub ecmp_nexthops_max: u32,
+   pub mac_entries: u32,
+   pub mac_entries_max: u32,
+   pub ipv4_neighbors: u32,
+   pub ipv6_neighbors: u32,
+   pub ipv4_routes: u32,
+   pub ipv6_routes: u32,
+   pub acl_ingress_entries: u32,
+   pub acl_ingress_entries_max: u32,
+   pub acl_ingress_counters: u32,
+   pub acl_ingress_counters_max: u32,
+   pub acl_ingress_meters: u32,
+   pub acl_ingress_meters_max: u32,
+   pub acl_ingress_slices: u32,
+   pub acl_ingress_slices_max: u32,
+   pub acl_egress_entries: u32,
+   pub acl_egress_entries_max: u32,
+   pub acl_egress_counters: u32,
+   pub acl_egress_counters_max: u32,
+   pub acl_egress_meters: u32,
+   pub acl_egress_meters_max: u32,
+   pub acl_egress_slices: u32,
+   pub acl_egress_slices_max: u32,
+}
+}
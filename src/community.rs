@@ -6,29 +6,61 @@ use types;
 use utils::Decodeable;
 use utils::ReadBytesLocal;
 
-/// Community represents a BGP community. While normally a community is a u32 with the first
-/// half being the asn, and the second half being a tag or value, in this case we are storing the
-/// asn as a u32 in order to support extended asns.
-#[derive(Debug, Copy, Clone, Default)]
+/// Community represents a BGP community (RFC 1997). On the wire a community is a single u32 where
+/// by convention the high 16 bits are an asn and the low 16 bits are a tag or value. Extended and
+/// large communities, which are needed for 4 byte asns, are not carried by sFlow.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Community {
-    pub asn: u32,
-    pub tag: u16,
+    pub value: u32,
+}
+
+impl Community {
+    /// asn returns the high 16 bits of the community.
+    pub fn asn(&self) -> u16 {
+        (self.value >> 16) as u16
+    }
+
+    /// tag returns the low 16 bits of the community.
+    pub fn tag(&self) -> u16 {
+        (self.value & 0xffff) as u16
+    }
+
+    /// well_known returns the well-known community this is, if any.
+    pub fn well_known(&self) -> Option<WellKnownCommunity> {
+        match self.value {
+            0xffff0000 => Some(WellKnownCommunity::GracefulShutdown),
+            0xffff029a => Some(WellKnownCommunity::Blackhole),
+            0xffffff01 => Some(WellKnownCommunity::NoExport),
+            0xffffff02 => Some(WellKnownCommunity::NoAdvertise),
+            0xffffff03 => Some(WellKnownCommunity::NoExportSubconfed),
+            0xffffff04 => Some(WellKnownCommunity::NoPeer),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Community {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}:{}", self.asn, self.tag)
+        write!(f, "{}:{}", self.asn(), self.tag())
     }
 }
 
 impl Decodeable for Community {
     #[inline]
     fn read_and_decode(stream: &mut types::ReadSeeker) -> Result<Community, error::Error> {
-        let r = Community {
-            asn: try!(stream.be_read_u16()) as u32,
-            tag: try!(stream.be_read_u16()),
-        };
+        let r = Community { value: try!(stream.be_read_u32()) };
 
         Ok(r)
     }
 }
+
+/// WellKnownCommunity are the IANA registered communities with a meaning shared across networks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum WellKnownCommunity {
+    GracefulShutdown, // 65535:0, RFC 8326
+    Blackhole, // 65535:666, RFC 7999
+    NoExport, // 65535:65281, RFC 1997
+    NoAdvertise, // 65535:65282, RFC 1997
+    NoExportSubconfed, // 65535:65283, RFC 1997
+    NoPeer, // 65535:65284, RFC 3765
+}
@@ -0,0 +1,417 @@
+// Local Imports
+use error;
+use macaddress::MACAddress;
+use raw::RawRecord;
+use registry::{self, CustomRecord};
+use types::{split_data_format, ReadSeeker};
+use utils::{read_body, ReadBytesLocal};
+use wireless::*;
+use application::*;
+use jvm::*;
+use optical::*;
+use broadcom::*;
+use nvml::*;
+use environmental::*;
+use host::*;
+
+#[derive(Debug, Clone)]
+pub enum CounterRecord {
+    GenericInterface(GenericInterface), // Format 1
+    Ethernet(Ethernet), // Format 2
+    TokenRing(TokenRing), // Format 3
+    Vg(Vg), // Format 4
+    Vlan(Vlan), // Format 5
+    Ieee80211Counters(Ieee80211Counters), // Format 6
+    LagPortStats(LagPortStats), // Format 7
+    Sfp(Sfp), // Format 10
+    Processor(Processor), // Format 1001
+    RadioUtilization(RadioUtilization), // Format 1002
+    OfPort(OfPort), // Format 1004
+    PortName(PortName), // Format 1005
+    HostDescr(HostDescr), // Format 2000
+    HostAdapters(HostAdapters), // Format 2001
+    HostParent(HostParent), // Format 2002
+    HostCpu(HostCpu), // Format 2003
+    HostMemory(HostMemory), // Format 2004
+    HostDiskIo(HostDiskIo), // Format 2005
+    HostNetIo(HostNetIo), // Format 2006
+    Mib2IpGroup(Mib2IpGroup), // Format 2007
+    Mib2IcmpGroup(Mib2IcmpGroup), // Format 2008
+    Mib2TcpGroup(Mib2TcpGroup), // Format 2009
+    Mib2UdpGroup(Mib2UdpGroup), // Format 2010
+    VirtNode(VirtNode), // Format 2100
+    VirtCpu(VirtCpu), // Format 2101
+    VirtMemory(VirtMemory), // Format 2102
+    VirtDiskIo(VirtDiskIo), // Format 2103
+    VirtNetIo(VirtNetIo), // Format 2104
+    JvmRuntime(JvmRuntime), // Format 2105
+    Jmx(Jmx), // Format 2106
+    HttpCounters(HttpCounters), // Format 2201
+    AppOperations(AppOperations), // Format 2202
+    AppResources(AppResources), // Format 2203
+    MemcacheCounters(MemcacheCounters), // Format 2204
+    AppWorkers(AppWorkers), // Format 2206
+    Energy(Energy), // Format 3000
+    Temperature(Temperature), // Format 3001
+    Humidity(Humidity), // Format 3002
+    Fans(Fans), // Format 3003
+    BstDeviceBuffers(BstDeviceBuffers), // Enterprise 4413, Format 1
+    BstPortBuffers(BstPortBuffers), // Enterprise 4413, Format 2
+    BcmTables(BcmTables), // Enterprise 4413, Format 3
+    NvidiaGpu(NvidiaGpu), // Enterprise 5703, Format 1
+    Custom(CustomRecord), // Any format with a decoder in the registry
+    RawRecord(RawRecord), // Any other format
+}
+
+impl ::utils::Decodeable for CounterRecord {
+    fn read_and_decode(stream: &mut ReadSeeker) -> Result<CounterRecord, error::Error> {
+        let (enterprise, format) = split_data_format(try!(stream.be_read_u32()));
+        let length = try!(stream.be_read_u32());
+
+        let mut body = try!(read_body(stream, length));
+
+        match (enterprise, format) {
+            (0, 1) => {
+                let e = try!(GenericInterface::read_and_decode(&mut body));
+                return Ok(CounterRecord::GenericInterface(e));
+            }
+            (0, 2) => {
+                let e = try!(Ethernet::read_and_decode(&mut body));
+                return Ok(CounterRecord::Ethernet(e));
+            }
+            (0, 3) => {
+                let e = try!(TokenRing::read_and_decode(&mut body));
+                return Ok(CounterRecord::TokenRing(e));
+            }
+            (0, 4) => {
+                let e = try!(Vg::read_and_decode(&mut body));
+                return Ok(CounterRecord::Vg(e));
+            }
+            (0, 5) => {
+                let e = try!(Vlan::read_and_decode(&mut body));
+                return Ok(CounterRecord::Vlan(e));
+            }
+            (0, 6) => {
+                let e = try!(Ieee80211Counters::read_and_decode(&mut body));
+                return Ok(CounterRecord::Ieee80211Counters(e));
+            }
+            (0, 7) => {
+                let e = try!(LagPortStats::read_and_decode(&mut body));
+                return Ok(CounterRecord::LagPortStats(e));
+            }
+            (0, 10) => {
+                let e = try!(Sfp::read_and_decode(&mut body));
+                return Ok(CounterRecord::Sfp(e));
+            }
+            (0, 1001) => {
+                let e = try!(Processor::read_and_decode(&mut body));
+                return Ok(CounterRecord::Processor(e));
+            }
+            (0, 1002) => {
+                let e = try!(RadioUtilization::read_and_decode(&mut body));
+                return Ok(CounterRecord::RadioUtilization(e));
+            }
+            (0, 1004) => {
+                let e = try!(OfPort::read_and_decode(&mut body));
+                return Ok(CounterRecord::OfPort(e));
+            }
+            (0, 1005) => {
+                let e = try!(PortName::read_and_decode(&mut body));
+                return Ok(CounterRecord::PortName(e));
+            }
+            (0, 2000) => {
+                let e = try!(HostDescr::read_and_decode(&mut body));
+                return Ok(CounterRecord::HostDescr(e));
+            }
+            (0, 2001) => {
+                let e = try!(HostAdapters::read_and_decode(&mut body));
+                return Ok(CounterRecord::HostAdapters(e));
+            }
+            (0, 2002) => {
+                let e = try!(HostParent::read_and_decode(&mut body));
+                return Ok(CounterRecord::HostParent(e));
+            }
+            (0, 2003) => {
+                let e = try!(HostCpu::read_and_decode(&mut body));
+                return Ok(CounterRecord::HostCpu(e));
+            }
+            (0, 2004) => {
+                let e = try!(HostMemory::read_and_decode(&mut body));
+                return Ok(CounterRecord::HostMemory(e));
+            }
+            (0, 2005) => {
+                let e = try!(HostDiskIo::read_and_decode(&mut body));
+                return Ok(CounterRecord::HostDiskIo(e));
+            }
+            (0, 2006) => {
+                let e = try!(HostNetIo::read_and_decode(&mut body));
+                return Ok(CounterRecord::HostNetIo(e));
+            }
+            (0, 2007) => {
+                let e = try!(Mib2IpGroup::read_and_decode(&mut body));
+                return Ok(CounterRecord::Mib2IpGroup(e));
+            }
+            (0, 2008) => {
+                let e = try!(Mib2IcmpGroup::read_and_decode(&mut body));
+                return Ok(CounterRecord::Mib2IcmpGroup(e));
+            }
+            (0, 2009) => {
+                let e = try!(Mib2TcpGroup::read_and_decode(&mut body));
+                return Ok(CounterRecord::Mib2TcpGroup(e));
+            }
+            (0, 2010) => {
+                let e = try!(Mib2UdpGroup::read_and_decode(&mut body));
+                return Ok(CounterRecord::Mib2UdpGroup(e));
+            }
+            (0, 2100) => {
+                let e = try!(VirtNode::read_and_decode(&mut body));
+                return Ok(CounterRecord::VirtNode(e));
+            }
+            (0, 2101) => {
+                let e = try!(VirtCpu::read_and_decode(&mut body));
+                return Ok(CounterRecord::VirtCpu(e));
+            }
+            (0, 2102) => {
+                let e = try!(VirtMemory::read_and_decode(&mut body));
+                return Ok(CounterRecord::VirtMemory(e));
+            }
+            (0, 2103) => {
+                let e = try!(VirtDiskIo::read_and_decode(&mut body));
+                return Ok(CounterRecord::VirtDiskIo(e));
+            }
+            (0, 2104) => {
+                let e = try!(VirtNetIo::read_and_decode(&mut body));
+                return Ok(CounterRecord::VirtNetIo(e));
+            }
+            (0, 2105) => {
+                let e = try!(JvmRuntime::read_and_decode(&mut body));
+                return Ok(CounterRecord::JvmRuntime(e));
+            }
+            (0, 2106) => {
+                let e = try!(Jmx::read_and_decode(&mut body));
+                return Ok(CounterRecord::Jmx(e));
+            }
+            (0, 2201) => {
+                let e = try!(HttpCounters::read_and_decode(&mut body));
+                return Ok(CounterRecord::HttpCounters(e));
+            }
+            (0, 2202) => {
+                let e = try!(AppOperations::read_and_decode(&mut body));
+                return Ok(CounterRecord::AppOperations(e));
+            }
+            (0, 2203) => {
+                let e = try!(AppResources::read_and_decode(&mut body));
+                return Ok(CounterRecord::AppResources(e));
+            }
+            (0, 2204) => {
+                let e = try!(MemcacheCounters::read_and_decode(&mut body));
+                return Ok(CounterRecord::MemcacheCounters(e));
+            }
+            (0, 2206) => {
+                let e = try!(AppWorkers::read_and_decode(&mut body));
+                return Ok(CounterRecord::AppWorkers(e));
+            }
+            (0, 3000) => {
+                let e = try!(Energy::read_and_decode(&mut body));
+                return Ok(CounterRecord::Energy(e));
+            }
+            (0, 3001) => {
+                let e = try!(Temperature::read_and_decode(&mut body));
+                return Ok(CounterRecord::Temperature(e));
+            }
+            (0, 3002) => {
+                let e = try!(Humidity::read_and_decode(&mut body));
+                return Ok(CounterRecord::Humidity(e));
+            }
+            (0, 3003) => {
+                let e = try!(Fans::read_and_decode(&mut body));
+                return Ok(CounterRecord::Fans(e));
+            }
+            (BROADCOM, 1) => {
+                let e = try!(BstDeviceBuffers::read_and_decode(&mut body));
+                return Ok(CounterRecord::BstDeviceBuffers(e));
+            }
+            (BROADCOM, 2) => {
+                let e = try!(BstPortBuffers::read_and_decode(&mut body));
+                return Ok(CounterRecord::BstPortBuffers(e));
+            }
+            (BROADCOM, 3) => {
+                let e = try!(BcmTables::read_and_decode(&mut body));
+                return Ok(CounterRecord::BcmTables(e));
+            }
+            (NVIDIA, 1) => {
+                let e = try!(NvidiaGpu::read_and_decode(&mut body));
+                return Ok(CounterRecord::NvidiaGpu(e));
+            }
+            _ => {
+                let data = body.into_inner();
+                let decoded = registry::decode_unregistered(registry::counter_decoder,
+                                                            enterprise,
+                                                            format,
+                                                            data);
+                match decoded {
+                    Ok(e) => return Ok(CounterRecord::Custom(e)),
+                    Err(r) => return Ok(CounterRecord::RawRecord(r)),
+                }
+            }
+        }
+    }
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct GenericInterface {
+   pub if_index: u32,
+   pub if_type: u32,
+   pub if_speed: u64,
+   pub if_direction: u32,     /* Derived from MAU MIB (RFC 2668)
+                                 0 = unknown, 1 = full-duplex,
+                                 2 = half-duplex, 3 = in, 4 = out */
+   pub if_status: u32,        /* bit field with the following bits assigned:
+                                 bit 0 = ifAdminStatus (0 = down, 1 = up)
+                                 bit 1 = ifOperStatus (0 = down, 1 = up) */
+   pub if_in_octets: u64,
+   pub if_in_ucast_pkts: u32,
+   pub if_in_multicast_pkts: u32,
+   pub if_in_broadcast_pkts: u32,
+   pub if_in_discards: u32,
+   pub if_in_errors: u32,
+   pub if_in_unknown_protos: u32,
+   pub if_out_octets: u64,
+   pub if_out_ucast_pkts: u32,
+   pub if_out_multicast_pkts: u32,
+   pub if_out_broadcast_pkts: u32,
+   pub if_out_discards: u32,
+   pub if_out_errors: u32,
+   pub if_promiscuous_mode: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Ethernet {
+   pub dot3_stats_alignment_errors: u32,
+   pub dot3_stats_fcs_errors: u32,
+   pub dot3_stats_single_collision_frames: u32,
+   pub dot3_stats_multiple_collision_frames: u32,
+   pub dot3_stats_sqe_test_errors: u32,
+   pub dot3_stats_deferred_transmissions: u32,
+   pub dot3_stats_late_collisions: u32,
+   pub dot3_stats_excessive_collisions: u32,
+   pub dot3_stats_internal_mac_transmit_errors: u32,
+   pub dot3_stats_carrier_sense_errors: u32,
+   pub dot3_stats_frame_too_longs: u32,
+   pub dot3_stats_internal_mac_receive_errors: u32,
+   pub dot3_stats_symbol_errors: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct TokenRing {
+   pub dot5_stats_line_errors: u32,
+   pub dot5_stats_burst_errors: u32,
+   pub dot5_stats_ac_errors: u32,
+   pub dot5_stats_abort_trans_errors: u32,
+   pub dot5_stats_internal_errors: u32,
+   pub dot5_stats_lost_frame_errors: u32,
+   pub dot5_stats_receive_congestions: u32,
+   pub dot5_stats_frame_copied_errors: u32,
+   pub dot5_stats_token_errors: u32,
+   pub dot5_stats_soft_errors: u32,
+   pub dot5_stats_hard_errors: u32,
+   pub dot5_stats_signal_loss: u32,
+   pub dot5_stats_transmit_beacons: u32,
+   pub dot5_stats_recoverys: u32,
+   pub dot5_stats_lobe_wires: u32,
+   pub dot5_stats_removes: u32,
+   pub dot5_stats_singles: u32,
+   pub dot5_stats_freq_errors: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Vg {
+   pub dot12_in_high_priority_frames: u32,
+   pub dot12_in_high_priority_octets: u64,
+   pub dot12_in_norm_priority_frames: u32,
+   pub dot12_in_norm_priority_octets: u64,
+   pub dot12_in_ipm_errors: u32,
+   pub dot12_in_oversize_frame_errors: u32,
+   pub dot12_in_data_errors: u32,
+   pub dot12_in_null_addressed_frames: u32,
+   pub dot12_out_high_priority_frames: u32,
+   pub dot12_out_high_priority_octets: u64,
+   pub dot12_transition_into_trainings: u32,
+   pub dot12_hc_in_high_priority_octets: u64,
+   pub dot12_hc_in_norm_priority_octets: u64,
+   pub dot12_hc_out_high_priority_octets: u64,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Vlan {
+   pub vlan_id: u32,
+   pub octets: u64,
+   pub ucast_pkts: u32,
+   pub multicast_pkts: u32,
+   pub broadcast_pkts: u32,
+   pub discards: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Processor {
+   pub cpu_5s: i32,        /* 5 second average CPU utilization, in 100ths of a percent */
+   pub cpu_1m: i32,        /* 1 minute average CPU utilization, in 100ths of a percent */
+   pub cpu_5m: i32,        /* 5 minute average CPU utilization, in 100ths of a percent */
+   pub total_memory: u64,  /* total memory (in bytes) */
+   pub free_memory: u64,   /* free memory (in bytes) */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct OfPort {
+   pub datapath_id: u64, /* OpenFlow datapath ID */
+   pub port_no: u32,     /* OpenFlow port number */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct PortName {
+   pub name: String, /* Port name, e.g. the ifName */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct LagPortStats {
+   pub dot3ad_agg_port_actor_system_id: MACAddress,
+   pub dot3ad_agg_port_partner_system_id: MACAddress,
+   pub dot3ad_agg_port_attached_agg_id: u32,
+   pub dot3ad_agg_port_state: LagPortState,
+   pub dot3ad_agg_port_stats_lacpdus_rx: u32,
+   pub dot3ad_agg_port_stats_marker_pdus_rx: u32,
+   pub dot3ad_agg_port_stats_marker_response_pdus_rx: u32,
+   pub dot3ad_agg_port_stats_unknown_rx: u32,
+   pub dot3ad_agg_port_stats_illegal_rx: u32,
+   pub dot3ad_agg_port_stats_lacpdus_tx: u32,
+   pub dot3ad_agg_port_stats_marker_pdus_tx: u32,
+   pub dot3ad_agg_port_stats_marker_response_pdus_tx: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LagPortState {
+   pub actor_admin: u8,   /* dot3adAggPortActorAdminState */
+   pub actor_oper: u8,    /* dot3adAggPortActorOperState */
+   pub partner_admin: u8, /* dot3adAggPortPartnerAdminState */
+   pub partner_oper: u8,  /* dot3adAggPortPartnerOperState */
+}
+}
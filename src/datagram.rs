@@ -1,15 +1,43 @@
 // Internal Imports
 use sample::SampleRecord;
 use ipaddress::IPAddress;
+use error::{Error, Result};
+use legacy;
+use types::ReadSeeker;
+use utils::{Decodeable, ReadBytesLocal};
 
-add_decoder!{
 #[derive(Debug, Clone)]
 pub struct Datagram {
+    // Version 2, 4 or 5. Older versions are decoded into the version 5 model.
     pub sflow_version: u32,
     pub agent_address: IPAddress,
+
+    // Version 2 and 4 datagrams have no sub agent, in which case this is always 0.
     pub sub_agent_id: u32,
     pub sequence_number: u32,
     pub uptime: u32,
     pub sample_record: Vec<SampleRecord>,
 }
+
+impl Decodeable for Datagram {
+    fn read_and_decode(stream: &mut ReadSeeker) -> Result<Datagram> {
+        let version = try!(stream.be_read_u32());
+
+        match version {
+            5 => {
+                let d = Datagram {
+                    sflow_version: version,
+                    agent_address: try!(Decodeable::read_and_decode(stream)),
+                    sub_agent_id: try!(Decodeable::read_and_decode(stream)),
+                    sequence_number: try!(Decodeable::read_and_decode(stream)),
+                    uptime: try!(Decodeable::read_and_decode(stream)),
+                    sample_record: try!(Decodeable::read_and_decode(stream)),
+                };
+
+                Ok(d)
+            }
+            2 | 4 => legacy::read_datagram(stream, version),
+            _ => Err(Error::UnsupportedVersion(version)),
+        }
+    }
 }
@@ -0,0 +1,437 @@
+//! Dissect parses the raw bytes of a sampled packet header into a stack of protocol layers.
+//! Sampled headers are usually cut short, commonly at 128 bytes, so dissection stops at the first
+//! layer that does not fit in the bytes available and reports the header as truncated. Everything
+//! decoded before that point is kept.
+
+// Local Imports
+use header_protocol::HeaderProtocol;
+use macaddress::MACAddress;
+
+// Std Lib Imports
+use std::net;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const ETHERTYPE_QINQ: u16 = 0x88a8;
+const ETHERTYPE_QINQ_LEGACY: u16 = 0x9100;
+const ETHERTYPE_MPLS: u16 = 0x8847;
+const ETHERTYPE_MPLS_MULTICAST: u16 = 0x8848;
+
+const IP_PROTO_ICMP: u8 = 1;
+const IP_PROTO_TCP: u8 = 6;
+const IP_PROTO_UDP: u8 = 17;
+const IP_PROTO_ICMPV6: u8 = 58;
+
+// IPv6 extension headers which are skipped to find the upper layer protocol.
+const IPV6_HOP_BY_HOP: u8 = 0;
+const IPV6_ROUTING: u8 = 43;
+const IPV6_FRAGMENT: u8 = 44;
+const IPV6_AUTH: u8 = 51;
+const IPV6_DEST_OPTS: u8 = 60;
+
+/// Dissection is the result of dissecting a sampled header.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dissection {
+    /// layers holds the decoded layers, outermost first.
+    pub layers: Vec<Layer>,
+
+    /// truncated is set when the header ended part way through a layer.
+    pub truncated: bool,
+
+    /// payload_offset is the offset into the header of the first byte after the last layer.
+    pub payload_offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Layer {
+    Ethernet(EthernetHeader),
+    Vlan(VlanTag),
+    Mpls(MplsLabel),
+    Ipv4(Ipv4Header),
+    Ipv6(Ipv6Header),
+    Tcp(TcpHeader),
+    Udp(UdpHeader),
+    Icmp(IcmpHeader),
+    Icmpv6(IcmpHeader),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EthernetHeader {
+    pub dst: MACAddress,
+    pub src: MACAddress,
+    // EtherType, or the frame length for 802.3 frames when 1500 or less.
+    pub ethertype: u16,
+}
+
+/// VlanTag is an 802.1Q tag. Stacked 802.1ad (QinQ) tags appear as one layer each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VlanTag {
+    pub tpid: u16,
+    pub pcp: u8,
+    pub dei: bool,
+    pub vid: u16,
+    // EtherType of the encapsulated frame.
+    pub ethertype: u16,
+}
+
+/// MplsLabel is a single label stack entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MplsLabel {
+    pub label: u32,
+    pub tc: u8,
+    pub bottom_of_stack: bool,
+    pub ttl: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ipv4Header {
+    pub header_length: u8, // In bytes
+    pub dscp: u8,
+    pub ecn: u8,
+    pub total_length: u16,
+    pub identification: u16,
+    pub flags: u8,
+    pub fragment_offset: u16, // In units of 8 bytes
+    pub ttl: u8,
+    pub protocol: u8,
+    pub checksum: u16,
+    pub src: net::Ipv4Addr,
+    pub dst: net::Ipv4Addr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ipv6Header {
+    pub traffic_class: u8,
+    pub flow_label: u32,
+    pub payload_length: u16,
+    pub next_header: u8,
+    pub hop_limit: u8,
+    pub src: net::Ipv6Addr,
+    pub dst: net::Ipv6Addr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TcpHeader {
+    pub src_port: u16,
+    pub dst_port: u16,
+    pub sequence_number: u32,
+    pub ack_number: u32,
+    pub data_offset: u8, // In bytes
+    pub flags: u16,
+    pub window: u16,
+    pub checksum: u16,
+    pub urgent_pointer: u16,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UdpHeader {
+    pub src_port: u16,
+    pub dst_port: u16,
+    pub length: u16,
+    pub checksum: u16,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcmpHeader {
+    pub icmp_type: u8,
+    pub code: u8,
+    pub checksum: u16,
+}
+
+// Next is the layer to decode at the current offset.
+enum Next {
+    Ethernet,
+    Vlan(u16),
+    Mpls,
+    Ipv4,
+    Ipv6,
+    Ip(u8),
+    Done,
+}
+
+struct Dissector<'a> {
+    data: &'a [u8],
+    offset: usize,
+    result: Dissection,
+}
+
+impl<'a> Dissector<'a> {
+    // take returns the next n bytes, or None if the header ends first.
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.data.len() - self.offset < n {
+            self.result.truncated = true;
+            return None;
+        }
+
+        let b = &self.data[self.offset..self.offset + n];
+        self.offset += n;
+        Some(b)
+    }
+
+    fn run(mut self, mut next: Next) -> Dissection {
+        loop {
+            let n = match next {
+                Next::Ethernet => self.ethernet(),
+                Next::Vlan(tpid) => self.vlan(tpid),
+                Next::Mpls => self.mpls(),
+                Next::Ipv4 => self.ipv4(),
+                Next::Ipv6 => self.ipv6(),
+                Next::Ip(p) => self.transport(p),
+                Next::Done => break,
+            };
+
+            match n {
+                Some(n) => next = n,
+                None => break,
+            }
+        }
+
+        self.result
+    }
+
+    fn push(&mut self, layer: Layer) {
+        self.result.layers.push(layer);
+        self.result.payload_offset = self.offset;
+    }
+
+    fn ethernet(&mut self) -> Option<Next> {
+        let b = self.take(14)?;
+        let e = EthernetHeader {
+            dst: mac(&b[0..6]),
+            src: mac(&b[6..12]),
+            ethertype: be_u16(&b[12..14]),
+        };
+        let next = ethertype_layer(e.ethertype);
+        self.push(Layer::Ethernet(e));
+
+        Some(next)
+    }
+
+    fn vlan(&mut self, tpid: u16) -> Option<Next> {
+        let b = self.take(4)?;
+        let tci = be_u16(&b[0..2]);
+        let v = VlanTag {
+            tpid,
+            pcp: (tci >> 13) as u8,
+            dei: tci & 0x1000 != 0,
+            vid: tci & 0x0fff,
+            ethertype: be_u16(&b[2..4]),
+        };
+        let next = ethertype_layer(v.ethertype);
+        self.push(Layer::Vlan(v));
+
+        Some(next)
+    }
+
+    fn mpls(&mut self) -> Option<Next> {
+        let b = self.take(4)?;
+        let entry = be_u32(b);
+        let m = MplsLabel {
+            label: entry >> 12,
+            tc: ((entry >> 9) & 0x7) as u8,
+            bottom_of_stack: entry & 0x100 != 0,
+            ttl: entry as u8,
+        };
+        let bottom_of_stack = m.bottom_of_stack;
+        self.push(Layer::Mpls(m));
+
+        if !bottom_of_stack {
+            return Some(Next::Mpls);
+        }
+
+        // MPLS does not identify its payload, guess from the IP version nibble.
+        match self.data.get(self.offset).map(|b| b >> 4) {
+            Some(4) => Some(Next::Ipv4),
+            Some(6) => Some(Next::Ipv6),
+            Some(_) => Some(Next::Done),
+            None => {
+                self.result.truncated = true;
+                None
+            }
+        }
+    }
+
+    fn ipv4(&mut self) -> Option<Next> {
+        let b = self.take(20)?;
+        let ihl = (b[0] & 0x0f) as usize * 4;
+        let frag = be_u16(&b[6..8]);
+        let ip = Ipv4Header {
+            header_length: ihl as u8,
+            dscp: b[1] >> 2,
+            ecn: b[1] & 0x3,
+            total_length: be_u16(&b[2..4]),
+            identification: be_u16(&b[4..6]),
+            flags: (frag >> 13) as u8,
+            fragment_offset: frag & 0x1fff,
+            ttl: b[8],
+            protocol: b[9],
+            checksum: be_u16(&b[10..12]),
+            src: net::Ipv4Addr::new(b[12], b[13], b[14], b[15]),
+            dst: net::Ipv4Addr::new(b[16], b[17], b[18], b[19]),
+        };
+
+        // Only the first fragment carries the transport header. A header length below the minimum
+        // is malformed, so nothing after it can be found.
+        let next = if ip.fragment_offset == 0 && ihl >= 20 {
+            Next::Ip(ip.protocol)
+        } else {
+            Next::Done
+        };
+        self.push(Layer::Ipv4(ip));
+
+        if ihl > 20 {
+            self.take(ihl - 20)?;
+            self.result.payload_offset = self.offset;
+        }
+
+        Some(next)
+    }
+
+    fn ipv6(&mut self) -> Option<Next> {
+        let b = self.take(40)?;
+        let first = be_u32(&b[0..4]);
+        let ip = Ipv6Header {
+            traffic_class: (first >> 20) as u8,
+            flow_label: first & 0x000f_ffff,
+            payload_length: be_u16(&b[4..6]),
+            next_header: b[6],
+            hop_limit: b[7],
+            src: ipv6(&b[8..24]),
+            dst: ipv6(&b[24..40]),
+        };
+        let mut next_header = ip.next_header;
+        self.push(Layer::Ipv6(ip));
+
+        loop {
+            match next_header {
+                IPV6_HOP_BY_HOP | IPV6_ROUTING | IPV6_DEST_OPTS => {
+                    let b = self.take(2)?;
+                    next_header = b[0];
+                    self.take(b[1] as usize * 8 + 6)?;
+                }
+                IPV6_AUTH => {
+                    let b = self.take(2)?;
+                    next_header = b[0];
+                    self.take(b[1] as usize * 4 + 6)?;
+                }
+                IPV6_FRAGMENT => {
+                    let b = self.take(8)?;
+                    next_header = b[0];
+                    if be_u16(&b[2..4]) >> 3 != 0 {
+                        return Some(Next::Done);
+                    }
+                }
+                _ => break,
+            }
+            self.result.payload_offset = self.offset;
+        }
+
+        Some(Next::Ip(next_header))
+    }
+
+    fn transport(&mut self, protocol: u8) -> Option<Next> {
+        match protocol {
+            IP_PROTO_TCP => {
+                let b = self.take(20)?;
+                let t = TcpHeader {
+                    src_port: be_u16(&b[0..2]),
+                    dst_port: be_u16(&b[2..4]),
+                    sequence_number: be_u32(&b[4..8]),
+                    ack_number: be_u32(&b[8..12]),
+                    data_offset: (b[12] >> 4) * 4,
+                    flags: be_u16(&b[12..14]) & 0x01ff,
+                    window: be_u16(&b[14..16]),
+                    checksum: be_u16(&b[16..18]),
+                    urgent_pointer: be_u16(&b[18..20]),
+                };
+                let data_offset = t.data_offset as usize;
+                self.push(Layer::Tcp(t));
+
+                // Skip the options so the payload offset is correct. A data offset below the
+                // minimum is malformed, so there is no telling where the payload starts.
+                if data_offset > 20 {
+                    self.take(data_offset - 20)?;
+                    self.result.payload_offset = self.offset;
+                }
+            }
+            IP_PROTO_UDP => {
+                let b = self.take(8)?;
+                let u = UdpHeader {
+                    src_port: be_u16(&b[0..2]),
+                    dst_port: be_u16(&b[2..4]),
+                    length: be_u16(&b[4..6]),
+                    checksum: be_u16(&b[6..8]),
+                };
+                self.push(Layer::Udp(u));
+            }
+            IP_PROTO_ICMP | IP_PROTO_ICMPV6 => {
+                let b = self.take(4)?;
+                let i = IcmpHeader {
+                    icmp_type: b[0],
+                    code: b[1],
+                    checksum: be_u16(&b[2..4]),
+                };
+                if protocol == IP_PROTO_ICMP {
+                    self.push(Layer::Icmp(i));
+                } else {
+                    self.push(Layer::Icmpv6(i));
+                }
+            }
+            _ => {}
+        }
+
+        Some(Next::Done)
+    }
+}
+
+// ethertype_layer returns the layer that follows an EtherType. For VLAN tags the EtherType is the
+// tag protocol identifier.
+fn ethertype_layer(ethertype: u16) -> Next {
+    match ethertype {
+        ETHERTYPE_IPV4 => Next::Ipv4,
+        ETHERTYPE_IPV6 => Next::Ipv6,
+        ETHERTYPE_MPLS | ETHERTYPE_MPLS_MULTICAST => Next::Mpls,
+        ETHERTYPE_VLAN | ETHERTYPE_QINQ | ETHERTYPE_QINQ_LEGACY => Next::Vlan(ethertype),
+        _ => Next::Done,
+    }
+}
+
+/// dissect dissects header starting at the layer given by protocol. Ethernet, IPv4, IPv6 and MPLS
+/// headers can be dissected, any other protocol gives no layers.
+pub fn dissect(protocol: HeaderProtocol, header: &[u8]) -> Dissection {
+    let next = match protocol {
+        HeaderProtocol::EthernetIso88023 => Next::Ethernet,
+        HeaderProtocol::Ipv4 => Next::Ipv4,
+        HeaderProtocol::Ipv6 => Next::Ipv6,
+        HeaderProtocol::Mpls => Next::Mpls,
+        _ => Next::Done,
+    };
+
+    let d = Dissector {
+        data: header,
+        offset: 0,
+        result: Dissection::default(),
+    };
+    d.run(next)
+}
+
+fn be_u16(b: &[u8]) -> u16 {
+    (b[0] as u16) << 8 | b[1] as u16
+}
+
+fn be_u32(b: &[u8]) -> u32 {
+    (be_u16(&b[0..2]) as u32) << 16 | be_u16(&b[2..4]) as u32
+}
+
+fn mac(b: &[u8]) -> MACAddress {
+    let mut m = [0; 6];
+    m.copy_from_slice(b);
+    MACAddress(m)
+}
+
+fn ipv6(b: &[u8]) -> net::Ipv6Addr {
+    let mut a = [0; 16];
+    a.copy_from_slice(b);
+    net::Ipv6Addr::from(a)
+}
@@ -0,0 +1,73 @@
+//! DropReason is the reason an agent gives for discarding a packet in a dropped packet
+//! notification. Codes below 256 mirror the ICMP destination unreachable codes, the rest are
+//! switch and host specific reasons.
+
+add_code_enum!{
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DropReason {
+    NetUnreachable = 0,
+    HostUnreachable = 1,
+    ProtocolUnreachable = 2,
+    PortUnreachable = 3,
+    FragNeeded = 4,
+    SrcRouteFailed = 5,
+    DstNetUnknown = 6, // ipv4_lpm_miss, ipv6_lpm_miss
+    DstHostUnknown = 7,
+    SrcHostIsolated = 8,
+    DstNetProhibited = 9, // reject_route
+    DstHostProhibited = 10,
+    DstNetTosUnreachable = 11,
+    DstHostTosUnreachable = 12,
+    CommAdminProhibited = 13,
+    HostPrecedenceViolation = 14,
+    PrecedenceCutoff = 15,
+    Unknown = 256,
+    TtlExceeded = 257, // ttl_value_is_too_small
+    Acl = 258, // ingress_flow_action_drop, egress_flow_action_drop, group_acl_drop
+    NoBufferSpace = 259, // tail_drop
+    Red = 260, // early_drop
+    TrafficShaping = 261,
+    PktTooBig = 262, // mtu_value_is_too_small
+    SrcMacIsMulticast = 263,
+    VlanTagMismatch = 264,
+    IngressVlanFilter = 265,
+    IngressSpanningTreeFilter = 266,
+    PortListIsEmpty = 267,
+    PortLoopbackFilter = 268,
+    BlackholeRoute = 269,
+    NonIp = 270,
+    UcDipOverMcDmac = 271,
+    DipIsLoopbackAddress = 272,
+    SipIsMc = 273,
+    SipIsLoopbackAddress = 274,
+    IpHeaderCorrupted = 275,
+    Ipv4SipIsLimitedBc = 276,
+    Ipv6McDipReservedScope = 277,
+    Ipv6McDipInterfaceLocalScope = 278,
+    UnresolvedNeigh = 279,
+    McReversePathForwarding = 280,
+    NonRoutablePacket = 281,
+    DecapError = 282,
+    OverlaySmacIsMc = 283,
+    UnknownL2 = 284,
+    UnknownL3 = 285,
+    UnknownL3Exception = 286,
+    UnknownBuffer = 287,
+    UnknownTunnel = 288,
+    UnknownL4 = 289,
+    SipIsUnspecified = 290,
+    MlagPortIsolation = 291,
+    BlackholeArpNeigh = 292,
+    SrcMacIsDmac = 293,
+    DmacIsReserved = 294,
+    SipIsClassE = 295,
+    McDmacMismatch = 296,
+    SipIsDip = 297,
+    DipIsLocalNetwork = 298,
+    DipIsLinkLocal = 299,
+    OverlaySmacIsDmac = 300,
+    EgressVlanFilter = 301,
+    UcReversePathForwarding = 302,
+    SplitHorizon = 303,
+}
+}
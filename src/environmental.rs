@@ -0,0 +1,102 @@
+//! Environmental holds the energy and environmental counter structures reported by PDUs and
+//! switches. Values are reported as integers in the units noted, helpers are provided to convert
+//! them to the natural unit.
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Energy {
+   pub voltage: u32,      /* millivolts */
+   pub current: u32,      /* milliamps */
+   pub real_power: u32,   /* milliwatts */
+   pub power_factor: i32, /* signed, percent */
+   pub energy: u32,       /* millijoules, total since last reset */
+   pub errors: u32,       /* total number of errors since last reset */
+}
+}
+
+impl Energy {
+    /// voltage_v returns the voltage in volts.
+    pub fn voltage_v(&self) -> f64 {
+        self.voltage as f64 / 1000.0
+    }
+
+    /// current_a returns the current in amps.
+    pub fn current_a(&self) -> f64 {
+        self.current as f64 / 1000.0
+    }
+
+    /// real_power_w returns the real power in watts.
+    pub fn real_power_w(&self) -> f64 {
+        self.real_power as f64 / 1000.0
+    }
+
+    /// power_factor_ratio returns the power factor as a ratio between -1 and 1.
+    pub fn power_factor_ratio(&self) -> f64 {
+        self.power_factor as f64 / 100.0
+    }
+
+    /// energy_j returns the energy in joules.
+    pub fn energy_j(&self) -> f64 {
+        self.energy as f64 / 1000.0
+    }
+
+    /// energy_kwh returns the energy in kilowatt hours.
+    pub fn energy_kwh(&self) -> f64 {
+        self.energy as f64 / 3_600_000_000.0
+    }
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Temperature {
+   pub minimum: i32, /* signed, degrees Celsius, minimum reading across sensors */
+   pub maximum: i32, /* signed, degrees Celsius, maximum reading across sensors */
+   pub errors: u32,  /* total number of errors since last reset */
+}
+}
+
+impl Temperature {
+    /// minimum_f returns the minimum reading in degrees Fahrenheit.
+    pub fn minimum_f(&self) -> f64 {
+        celsius_to_fahrenheit(self.minimum)
+    }
+
+    /// maximum_f returns the maximum reading in degrees Fahrenheit.
+    pub fn maximum_f(&self) -> f64 {
+        celsius_to_fahrenheit(self.maximum)
+    }
+}
+
+fn celsius_to_fahrenheit(c: i32) -> f64 {
+    c as f64 * 9.0 / 5.0 + 32.0
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Humidity {
+   pub relative: i32, /* signed, percent */
+}
+}
+
+impl Humidity {
+    /// relative_ratio returns the relative humidity as a ratio between 0 and 1.
+    pub fn relative_ratio(&self) -> f64 {
+        self.relative as f64 / 100.0
+    }
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Fans {
+   pub total: u32,  /* total number of fans */
+   pub failed: u32, /* number of failed fans */
+   pub speed: u32,  /* mean speed of fans, percent of maximum */
+}
+}
+
+impl Fans {
+    /// working returns the number of fans which have not failed.
+    pub fn working(&self) -> u32 {
+        self.total.saturating_sub(self.failed)
+    }
+}
@@ -10,7 +10,7 @@ use byteorder;
 pub enum Error {
     Io(io::Error),
     ByteOrder(byteorder::Error),
-    UnknownType(String),
+    UnsupportedVersion(u32),
     Utf8(FromUtf8Error),
 }
 
@@ -23,7 +23,7 @@ impl error::Error for Error {
             Error::Io(ref err) => error::Error::description(err),
             Error::ByteOrder(ref err) => error::Error::description(err),
             Error::Utf8(ref err) => error::Error::description(err),
-            Error::UnknownType(ref s) => &s,
+            Error::UnsupportedVersion(_) => "unsupported sflow version",
         }
     }
 
@@ -31,7 +31,7 @@ impl error::Error for Error {
         match *self {
             Error::Io(ref err) => err.cause(),
             Error::ByteOrder(ref err) => err.cause(),
-            Error::UnknownType(_) => None,
+            Error::UnsupportedVersion(_) => None,
             Error::Utf8(ref err) => err.cause(),
         }
     }
@@ -42,7 +42,7 @@ impl fmt::Display for Error {
         match *self {
             Error::Io(ref err) => err.fmt(f),
             Error::ByteOrder(ref err) => err.fmt(f),
-            Error::UnknownType(ref s) => write!(f, "unkown type {}", s),
+            Error::UnsupportedVersion(v) => write!(f, "unsupported sflow version {}", v),
             Error::Utf8(ref err) => err.fmt(f),
         }
     }
@@ -1,73 +1,299 @@
 // Local Imports
-use community::Community;
-use dst_as_path;
+use as_path::{AsPathSegment, AsPathSegmentKind};
+use community::{Community, WellKnownCommunity};
+use dissect::{self, Dissection};
 use error;
+use header_protocol::HeaderProtocol;
 use ipaddress;
-use types::ReadSeeker;
-use utils::ReadBytesLocal;
+use macaddress::MACAddress;
+use raw::RawRecord;
+use registry::{self, CustomRecord};
+use types::{split_data_format, ReadSeeker};
+use utils::{read_body, Decodeable, ReadBytesLocal};
+use wireless::*;
+use application::*;
+use broadcom::*;
 
 // Std Lib Imports
-use std::io::SeekFrom;
+use std::net;
 
 #[derive(Debug, Clone)]
 pub enum FlowRecord {
     SampledHeader(SampledHeader), // Format 1
+    SampledEthernet(SampledEthernet), // Format 2
     SampledIpv4(SampledIpv4), // Format 3
+    SampledIpv6(SampledIpv6), // Format 4
     ExtendedSwitch(ExtendedSwitch), // Format 1001
     ExtendedRouter(ExtendedRouter), // Format 1002
     ExtendedGateway(ExtendedGateway), // Format 1003
+    ExtendedUser(ExtendedUser), // Format 1004
     ExtendedUrl(ExtendedUrl), // Format 1005
     ExtendedMpls(ExtendedMpls), // Format 1006
+    ExtendedNat(ExtendedNat), // Format 1007
     ExtendedMplsTunnel(ExtendedMplsTunnel), // Format 1008
+    ExtendedMplsVc(ExtendedMplsVc), // Format 1009
+    ExtendedMplsFtn(ExtendedMplsFtn), // Format 1010
+    ExtendedMplsLdpFec(ExtendedMplsLdpFec), // Format 1011
+    ExtendedVlanTunnel(ExtendedVlanTunnel), // Format 1012
+    Extended80211Payload(Extended80211Payload), // Format 1013
+    Extended80211Rx(Extended80211Rx), // Format 1014
+    Extended80211Tx(Extended80211Tx), // Format 1015
+    Extended80211Aggregation(Extended80211Aggregation), // Format 1016
+    ExtendedL2TunnelEgress(SampledEthernet), // Format 1021
+    ExtendedL2TunnelIngress(SampledEthernet), // Format 1022
+    ExtendedIpv4TunnelEgress(SampledIpv4), // Format 1023
+    ExtendedIpv4TunnelIngress(SampledIpv4), // Format 1024
+    ExtendedIpv6TunnelEgress(SampledIpv6), // Format 1025
+    ExtendedIpv6TunnelIngress(SampledIpv6), // Format 1026
+    ExtendedDecapsulateEgress(ExtendedDecapsulate), // Format 1027
+    ExtendedDecapsulateIngress(ExtendedDecapsulate), // Format 1028
+    ExtendedVniEgress(ExtendedVni), // Format 1029
+    ExtendedVniIngress(ExtendedVni), // Format 1030
+    ExtendedEgressQueue(ExtendedEgressQueue), // Format 1036
+    ExtendedAcl(ExtendedAcl), // Format 1037
+    ExtendedFunction(ExtendedFunction), // Format 1038
+    ExtendedTransit(ExtendedTransit), // Format 1039
+    ExtendedQueue(ExtendedQueue), // Format 1040
+    ExtendedHwTrap(ExtendedHwTrap), // Format 1041
+    ExtendedLinuxDropReason(ExtendedLinuxDropReason), // Format 1042
+    ExtendedSocketIpv4(ExtendedSocketIpv4), // Format 2100
+    ExtendedSocketIpv6(ExtendedSocketIpv6), // Format 2101
+    ExtendedProxySocketIpv4(ExtendedSocketIpv4), // Format 2102
+    ExtendedProxySocketIpv6(ExtendedSocketIpv6), // Format 2103
+    MemcacheOperation(MemcacheOperation), // Format 2200
+    AppOperation(AppOperation), // Format 2202
+    AppParentContext(AppParentContext), // Format 2203
+    AppInitiator(AppInitiator), // Format 2204
+    AppTarget(AppTarget), // Format 2205
+    HttpRequest(HttpRequest), // Format 2206
+    BstEgressQueue(BstEgressQueue), // Enterprise 4413, Format 1
+    Custom(CustomRecord), // Any format with a decoder in the registry
+    RawRecord(RawRecord), // Any other format
 }
 
+// MAX_AGGREGATION_DEPTH is how deeply 802.11 aggregations may nest. An aggregation holds the
+// records of the frames it aggregates, so one inside another has no meaning; past this depth it
+// is kept raw rather than recursed into, which would let a crafted datagram exhaust the stack.
+const MAX_AGGREGATION_DEPTH: u32 = 1;
+
 impl ::utils::Decodeable for FlowRecord {
     fn read_and_decode(stream: &mut ReadSeeker) -> Result<FlowRecord, error::Error> {
-        let format = try!(stream.be_read_u32());
+        FlowRecord::read_nested(stream, 0)
+    }
+}
+
+impl FlowRecord {
+    /// read_nested decodes a flow record found depth 802.11 aggregations deep.
+    pub(crate) fn read_nested(stream: &mut ReadSeeker, depth: u32)
+                              -> Result<FlowRecord, error::Error> {
+        let (enterprise, format) = split_data_format(try!(stream.be_read_u32()));
         let length = try!(stream.be_read_u32());
 
-        match format {
-            1 => {
-                let e = try!(SampledHeader::read_and_decode(stream));
+        let mut body = try!(read_body(stream, length));
+
+        match (enterprise, format) {
+            (0, 1) => {
+                let e = try!(SampledHeader::read_and_decode(&mut body));
                 return Ok(FlowRecord::SampledHeader(e));
             }
-            3 => {
-                let e = try!(SampledIpv4::read_and_decode(stream));
+            (0, 2) => {
+                let e = try!(SampledEthernet::read_and_decode(&mut body));
+                return Ok(FlowRecord::SampledEthernet(e));
+            }
+            (0, 3) => {
+                let e = try!(SampledIpv4::read_and_decode(&mut body));
                 return Ok(FlowRecord::SampledIpv4(e));
             }
-            1001 => {
-                let e = try!(ExtendedSwitch::read_and_decode(stream));
+            (0, 4) => {
+                let e = try!(SampledIpv6::read_and_decode(&mut body));
+                return Ok(FlowRecord::SampledIpv6(e));
+            }
+            (0, 1001) => {
+                let e = try!(ExtendedSwitch::read_and_decode(&mut body));
                 return Ok(FlowRecord::ExtendedSwitch(e));
             }
-            1002 => {
-                let e = try!(ExtendedRouter::read_and_decode(stream));
+            (0, 1002) => {
+                let e = try!(ExtendedRouter::read_and_decode(&mut body));
                 return Ok(FlowRecord::ExtendedRouter(e));
             }
-            1003 => {
-                let e = try!(ExtendedGateway::read_and_decode(stream));
+            (0, 1003) => {
+                let e = try!(ExtendedGateway::read_and_decode(&mut body));
                 return Ok(FlowRecord::ExtendedGateway(e));
             }
-            1005 => {
-                let e = try!(ExtendedUrl::read_and_decode(stream));
+            (0, 1004) => {
+                let e = try!(ExtendedUser::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedUser(e));
+            }
+            (0, 1005) => {
+                let e = try!(ExtendedUrl::read_and_decode(&mut body));
                 return Ok(FlowRecord::ExtendedUrl(e));
             }
-            1006 => {
-                let e = try!(ExtendedMpls::read_and_decode(stream));
+            (0, 1006) => {
+                let e = try!(ExtendedMpls::read_and_decode(&mut body));
                 return Ok(FlowRecord::ExtendedMpls(e));
             }
-            1008 => {
-                let e = try!(ExtendedMplsTunnel::read_and_decode(stream));
+            (0, 1007) => {
+                let e = try!(ExtendedNat::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedNat(e));
+            }
+            (0, 1008) => {
+                let e = try!(ExtendedMplsTunnel::read_and_decode(&mut body));
                 return Ok(FlowRecord::ExtendedMplsTunnel(e));
             }
+            (0, 1009) => {
+                let e = try!(ExtendedMplsVc::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedMplsVc(e));
+            }
+            (0, 1010) => {
+                let e = try!(ExtendedMplsFtn::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedMplsFtn(e));
+            }
+            (0, 1011) => {
+                let e = try!(ExtendedMplsLdpFec::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedMplsLdpFec(e));
+            }
+            (0, 1012) => {
+                let e = try!(ExtendedVlanTunnel::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedVlanTunnel(e));
+            }
+            (0, 1013) => {
+                let e = try!(Extended80211Payload::read_and_decode(&mut body));
+                return Ok(FlowRecord::Extended80211Payload(e));
+            }
+            (0, 1014) => {
+                let e = try!(Extended80211Rx::read_and_decode(&mut body));
+                return Ok(FlowRecord::Extended80211Rx(e));
+            }
+            (0, 1015) => {
+                let e = try!(Extended80211Tx::read_and_decode(&mut body));
+                return Ok(FlowRecord::Extended80211Tx(e));
+            }
+            (0, 1016) if depth < MAX_AGGREGATION_DEPTH => {
+                let e = try!(Extended80211Aggregation::read_nested(&mut body, depth + 1));
+                return Ok(FlowRecord::Extended80211Aggregation(e));
+            }
+            (0, 1021) => {
+                let e = try!(SampledEthernet::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedL2TunnelEgress(e));
+            }
+            (0, 1022) => {
+                let e = try!(SampledEthernet::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedL2TunnelIngress(e));
+            }
+            (0, 1023) => {
+                let e = try!(SampledIpv4::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedIpv4TunnelEgress(e));
+            }
+            (0, 1024) => {
+                let e = try!(SampledIpv4::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedIpv4TunnelIngress(e));
+            }
+            (0, 1025) => {
+                let e = try!(SampledIpv6::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedIpv6TunnelEgress(e));
+            }
+            (0, 1026) => {
+                let e = try!(SampledIpv6::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedIpv6TunnelIngress(e));
+            }
+            (0, 1027) => {
+                let e = try!(ExtendedDecapsulate::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedDecapsulateEgress(e));
+            }
+            (0, 1028) => {
+                let e = try!(ExtendedDecapsulate::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedDecapsulateIngress(e));
+            }
+            (0, 1029) => {
+                let e = try!(ExtendedVni::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedVniEgress(e));
+            }
+            (0, 1030) => {
+                let e = try!(ExtendedVni::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedVniIngress(e));
+            }
+            (0, 1036) => {
+                let e = try!(ExtendedEgressQueue::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedEgressQueue(e));
+            }
+            (0, 1037) => {
+                let e = try!(ExtendedAcl::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedAcl(e));
+            }
+            (0, 1038) => {
+                let e = try!(ExtendedFunction::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedFunction(e));
+            }
+            (0, 1039) => {
+                let e = try!(ExtendedTransit::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedTransit(e));
+            }
+            (0, 1040) => {
+                let e = try!(ExtendedQueue::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedQueue(e));
+            }
+            (0, 1041) => {
+                let e = try!(ExtendedHwTrap::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedHwTrap(e));
+            }
+            (0, 1042) => {
+                let e = try!(ExtendedLinuxDropReason::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedLinuxDropReason(e));
+            }
+            (0, 2100) => {
+                let e = try!(ExtendedSocketIpv4::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedSocketIpv4(e));
+            }
+            (0, 2101) => {
+                let e = try!(ExtendedSocketIpv6::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedSocketIpv6(e));
+            }
+            (0, 2102) => {
+                let e = try!(ExtendedSocketIpv4::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedProxySocketIpv4(e));
+            }
+            (0, 2103) => {
+                let e = try!(ExtendedSocketIpv6::read_and_decode(&mut body));
+                return Ok(FlowRecord::ExtendedProxySocketIpv6(e));
+            }
+            (0, 2200) => {
+                let e = try!(MemcacheOperation::read_and_decode(&mut body));
+                return Ok(FlowRecord::MemcacheOperation(e));
+            }
+            (0, 2202) => {
+                let e = try!(AppOperation::read_and_decode(&mut body));
+                return Ok(FlowRecord::AppOperation(e));
+            }
+            (0, 2203) => {
+                let e = try!(AppParentContext::read_and_decode(&mut body));
+                return Ok(FlowRecord::AppParentContext(e));
+            }
+            (0, 2204) => {
+                let e = try!(AppInitiator::read_and_decode(&mut body));
+                return Ok(FlowRecord::AppInitiator(e));
+            }
+            (0, 2205) => {
+                let e = try!(AppTarget::read_and_decode(&mut body));
+                return Ok(FlowRecord::AppTarget(e));
+            }
+            (0, 2206) => {
+                let e = try!(HttpRequest::read_and_decode(&mut body));
+                return Ok(FlowRecord::HttpRequest(e));
+            }
+            (BROADCOM, 1) => {
+                let e = try!(BstEgressQueue::read_and_decode(&mut body));
+                return Ok(FlowRecord::BstEgressQueue(e));
+            }
             _ => {
-                println!("DEBUG: Unknown FlowRecord type {0} skipping {1} bytes.",
-                         format,
-                         length);
-                try!(stream.seek(SeekFrom::Current(length as i64)));
-                return Err(error::Error::UnknownType(format!("Unknown FlowRecord type {0} \
-                                                              skipping {1} bytes.",
-                                                             format,
-                                                             length)));
+                let data = body.into_inner();
+                let decoded = registry::decode_unregistered(registry::flow_decoder,
+                                                            enterprise,
+                                                            format,
+                                                            data);
+                match decoded {
+                    Ok(e) => return Ok(FlowRecord::Custom(e)),
+                    Err(r) => return Ok(FlowRecord::RawRecord(r)),
+                }
             }
         }
     }
@@ -80,12 +306,48 @@ pub struct ExtendedGateway {
     pub asn: u32, // Autonomous system number of router
     pub src_as: u32, // Autonomous system number of source
     pub src_peer_as: u32,   /* Autonomous system number of source peer */
-    pub dst_as_path: Vec<dst_as_path::DstASPath>, /* Autonomous system path to the destination */
+    pub dst_as_path: Vec<AsPathSegment>, /* Autonomous system path to the destination */
     pub communities: Vec<Community>, // Communities associated with this route
     pub localpref: u32, // LocalPref associated with this route
 }
 }
 
+impl ExtendedGateway {
+    /// origin_as returns the AS that originated the destination route, the last AS of the path.
+    /// None is returned for an empty path or when the path ends in an AS_SET, as the origin is
+    /// then ambiguous.
+    pub fn origin_as(&self) -> Option<u32> {
+        match self.dst_as_path.last() {
+            Some(seg) if seg.kind == AsPathSegmentKind::AsSequence => seg.asns.last().cloned(),
+            _ => None,
+        }
+    }
+
+    /// neighbor_as returns the AS the route was learned from, the first AS of the path.
+    pub fn neighbor_as(&self) -> Option<u32> {
+        match self.dst_as_path.first() {
+            Some(seg) if seg.kind == AsPathSegmentKind::AsSequence => seg.asns.first().cloned(),
+            _ => None,
+        }
+    }
+
+    /// path_length returns the AS path length as used by BGP best path selection.
+    pub fn path_length(&self) -> usize {
+        self.dst_as_path.iter().map(|seg| seg.path_length()).sum()
+    }
+
+    /// well_known_communities returns the well-known communities attached to this route.
+    pub fn well_known_communities(&self) -> Vec<WellKnownCommunity> {
+        self.communities.iter().filter_map(|c| c.well_known()).collect()
+    }
+
+    /// has_well_known_community returns whether the route carries the given community, for
+    /// example NO_EXPORT or BLACKHOLE.
+    pub fn has_well_known_community(&self, community: WellKnownCommunity) -> bool {
+        self.communities.iter().any(|c| c.well_known() == Some(community))
+    }
+}
+
 add_decoder!{
 #[derive(Debug, Clone)]
 pub struct ExtendedUrl {
@@ -102,8 +364,8 @@ pub struct SampledIpv4 {
                                   lower layer encapsulations */
    pub protocol: u32,   /* IP Protocol type
                                    (for example, TCP = 6, UDP = 17) */
-   pub src_ip: ipaddress::IPAddress,            /* Source IP Address */
-   pub dst_ip: ipaddress::IPAddress,            /* Destination IP Address */
+   pub src_ip: net::Ipv4Addr,            /* Source IP Address */
+   pub dst_ip: net::Ipv4Addr,            /* Destination IP Address */
    pub src_port: u32,   /* TCP/UDP source port number or equivalent */
    pub dst_port: u32,   /* TCP/UDP destination port number or equivalent */
    pub tcp_flags: u32,  /* TCP flags */
@@ -111,6 +373,34 @@ pub struct SampledIpv4 {
 }
 }
 
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct SampledIpv6 {
+   pub length: u32,     /* The length of the IP packet excluding
+                                  lower layer encapsulations */
+   pub protocol: u32,   /* IP next header
+                                   (for example, TCP = 6, UDP = 17) */
+   pub src_ip: net::Ipv6Addr,            /* Source IP Address */
+   pub dst_ip: net::Ipv6Addr,            /* Destination IP Address */
+   pub src_port: u32,   /* TCP/UDP source port number or equivalent */
+   pub dst_port: u32,   /* TCP/UDP destination port number or equivalent */
+   pub tcp_flags: u32,  /* TCP flags */
+   pub priority: u32,   /* IP priority */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct SampledEthernet {
+   pub length: u32,        /* The length of the MAC packet received on the network, excluding
+                              lower layer encapsulations and framing bits but including FCS
+                              octets */
+   pub src_mac: MACAddress, /* Source MAC address */
+   pub dst_mac: MACAddress, /* Destination MAC address */
+   pub eth_type: u32,      /* Ethernet packet type */
+}
+}
+
 add_decoder!{
 #[derive(Debug, Clone)]
 pub struct ExtendedSwitch {
@@ -135,8 +425,7 @@ pub struct ExtendedRouter {
 add_decoder!{
 #[derive(Debug, Clone)]
 pub struct SampledHeader {
-   // TODO: header_protocol should be an enum...
-   pub protocol: u32,       /* Format of sampled header */
+   pub protocol: HeaderProtocol, /* Format of sampled header */
    pub frame_length: u32, /* Original length of packet before sampling. Note: For a layer 2
                              header_protocol, length is total number of octets of data received on
                              the network (excluding framing bits but including FCS octets).
@@ -161,6 +450,14 @@ pub struct SampledHeader {
 }
 }
 
+impl SampledHeader {
+    /// dissect decodes the header bytes into protocol layers. Headers are often truncated, check
+    /// Dissection::truncated to tell whether all of the layers were present.
+    pub fn dissect(&self) -> Dissection {
+        dissect::dissect(self.protocol, &self.header)
+    }
+}
+
 add_decoder!{
 #[derive(Debug, Clone)]
 pub struct ExtendedMplsTunnel {
@@ -178,3 +475,127 @@ pub struct ExtendedMpls {
    pub out_stack: Vec<i32>,      /* Label stack for transmitted packet */
 }
 }
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedUser {
+   pub src_charset: u32,  /* Character set for src_user (IANA MIBEnum value, 106 = UTF-8) */
+   pub src_user: Vec<u8>, /* User ID associated with packet source */
+   pub dst_charset: u32,  /* Character set for dst_user */
+   pub dst_user: Vec<u8>, /* User ID associated with packet destination */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedNat {
+   pub src_address: ipaddress::IPAddress, /* Source address */
+   pub dst_address: ipaddress::IPAddress, /* Destination address */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedMplsVc {
+   pub vc_instance_name: String, /* VC instance name */
+   pub vll_vc_id: u32,           /* VLL/VC instance ID */
+   pub vc_label_cos: u32,        /* VC Label COS value */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedMplsFtn {
+   pub mpls_ftn_descr: String, /* FTN description */
+   pub mpls_ftn_mask: u32,     /* FTN mask */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedMplsLdpFec {
+   pub mpls_fec_addr_prefix_length: u32, /* FEC address prefix length */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedVlanTunnel {
+   pub stack: Vec<u32>, /* List of stripped 802.1Q TPID/TCI layers. Each TPID,TCI pair is
+                           represented as a single 32 bit integer. Layers listed from outermost
+                           to innermost. */
+}
+}
+
+// The tunnel egress and ingress records reuse SampledEthernet, SampledIpv4 and SampledIpv6 to
+// describe the outer (egress) or removed (ingress) encapsulation of the sampled packet.
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedDecapsulate {
+   pub inner_header_offset: u32, /* Offset into the sampled header of the inner header, or 0 if
+                                    the inner header was not included */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedVni {
+   pub vni: u32, /* VNI associated with the tunnel */
+}
+}
+
+// The following records accompany dropped packet notifications, describing where and why the
+// packet was discarded.
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedEgressQueue {
+   pub queue: u32, /* egress queue number selected for sampled packet */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedAcl {
+   pub number: u32,   /* access list number */
+   pub name: String,  /* access list name */
+   pub direction: u32, /* unknown = 0, ingress = 1, egress = 2 */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedFunction {
+   pub symbol: String, /* name of the function that dropped the packet */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedHwTrap {
+   pub group: String, /* NET_DM_ATTR_HW_TRAP_GROUP_NAME */
+   pub trap: String,  /* NET_DM_ATTR_HW_TRAP_NAME */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedLinuxDropReason {
+   pub reason: String, /* NET_DM_ATTR_REASON, the Linux kernel drop reason */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedTransit {
+   pub delay: u32, /* transit delay in nanoseconds, 0xffffffff indicates value >= 0xffffffff */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct ExtendedQueue {
+   pub depth: u32, /* queue depth in bytes */
+}
+}
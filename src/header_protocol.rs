@@ -0,0 +1,25 @@
+//! HeaderProtocol is the format of the bytes in a sampled packet header, the header_protocol of
+//! the sFlow specification.
+
+add_code_enum!{
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeaderProtocol {
+    EthernetIso88023 = 1,
+    Iso88024TokenBus = 2,
+    Iso88025TokenRing = 3,
+    Fddi = 4,
+    FrameRelay = 5,
+    X25 = 6,
+    Ppp = 7,
+    Smds = 8,
+    Aal5 = 9,
+    Aal5Ip = 10, // e.g. Cisco AAL5 mux
+    Ipv4 = 11,
+    Ipv6 = 12,
+    Mpls = 13,
+    Pos = 14, // RFC 1662, 2615
+    Ieee80211Mac = 15,
+    Ieee80211Ampdu = 16,
+    Ieee80211AmsduSubframe = 17,
+}
+}
@@ -0,0 +1,262 @@
+//! Host holds the counter structures exported by host sFlow agents such as hsflowd, describing
+//! the physical server along with its CPU, memory, disk and network usage.
+
+// Local Imports
+use macaddress::MACAddress;
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct HostDescr {
+   pub hostname: String,   /* hostname, empty if unknown */
+   pub uuid: [u8; 16],     /* 16 bytes binary UUID, all zeros if unknown */
+   pub machine_type: u32,  /* the processor family, e.g. 2 = other, 3 = x86, 7 = x86_64 */
+   pub os_name: u32,       /* the operating system, e.g. 2 = linux, 3 = windows */
+   pub os_release: String, /* e.g. 2.6.9-42.ELsmp, empty if unknown */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct HostAdapters {
+   pub adapters: Vec<HostAdapter>, /* adapter(s) associated with the entity */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct HostAdapter {
+   pub if_index: u32,                   /* ifIndex associated with adapter, must match ifIndex
+                                           of vSwitch port if vSwitch is exporting sFlow,
+                                           0 = unknown */
+   pub mac_addresses: Vec<MACAddress>,  /* Adapter MAC address(es) */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct HostParent {
+   pub container_type: u32,  /* sFlowDataSource type */
+   pub container_index: u32, /* sFlowDataSource index */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct HostCpu {
+   pub load_one: f32,       /* 1 minute load avg., -1.0 = unknown */
+   pub load_five: f32,      /* 5 minute load avg., -1.0 = unknown */
+   pub load_fifteen: f32,   /* 15 minute load avg., -1.0 = unknown */
+   pub proc_run: u32,       /* total number of running processes */
+   pub proc_total: u32,     /* total number of processes */
+   pub cpu_num: u32,        /* number of CPUs */
+   pub cpu_speed: u32,      /* speed in MHz of CPU */
+   pub uptime: u32,         /* seconds since last reboot */
+   pub cpu_user: u32,       /* user time (ms) */
+   pub cpu_nice: u32,       /* nice time (ms) */
+   pub cpu_system: u32,     /* system time (ms) */
+   pub cpu_idle: u32,       /* idle time (ms) */
+   pub cpu_wio: u32,        /* time waiting for I/O to complete (ms) */
+   pub cpu_intr: u32,       /* time servicing interrupts (ms) */
+   pub cpu_sintr: u32,      /* time servicing soft interrupts (ms) */
+   pub interrupts: u32,     /* interrupt count */
+   pub contexts: u32,       /* context switch count */
+   pub cpu_steal: u32,      /* time spent in other OS instances (virtual env) (ms) */
+   pub cpu_guest: u32,      /* time spent running vcpu for guest OS (ms) */
+   pub cpu_guest_nice: u32, /* time spent running vcpu for "niced" guest OS (ms) */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct HostMemory {
+   pub mem_total: u64,   /* total bytes */
+   pub mem_free: u64,    /* free bytes */
+   pub mem_shared: u64,  /* shared bytes */
+   pub mem_buffers: u64, /* buffers bytes */
+   pub mem_cached: u64,  /* cached bytes */
+   pub swap_total: u64,  /* swap total bytes */
+   pub swap_free: u64,   /* swap free bytes */
+   pub page_in: u32,     /* page in count */
+   pub page_out: u32,    /* page out count */
+   pub swap_in: u32,     /* swap in count */
+   pub swap_out: u32,    /* swap out count */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct HostDiskIo {
+   pub disk_total: u64,    /* total disk size in bytes */
+   pub disk_free: u64,     /* total disk free in bytes */
+   pub part_max_used: i32, /* utilization of most utilized partition, in 100ths of a percent */
+   pub reads: u32,         /* reads issued */
+   pub bytes_read: u64,    /* bytes read */
+   pub read_time: u32,     /* read time (ms) */
+   pub writes: u32,        /* writes completed */
+   pub bytes_written: u64, /* bytes written */
+   pub write_time: u32,    /* write time (ms) */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct HostNetIo {
+   pub bytes_in: u64,  /* total bytes in */
+   pub pkts_in: u32,   /* total packets in */
+   pub errs_in: u32,   /* total errors in */
+   pub drops_in: u32,  /* total drops in */
+   pub bytes_out: u64, /* total bytes out */
+   pub pkts_out: u32,  /* total packets out */
+   pub errs_out: u32,  /* total errors out */
+   pub drops_out: u32, /* total drops out */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Mib2IpGroup {
+   pub ip_forwarding: u32,
+   pub ip_default_ttl: u32,
+   pub ip_in_receives: u32,
+   pub ip_in_hdr_errors: u32,
+   pub ip_in_addr_errors: u32,
+   pub ip_forw_datagrams: u32,
+   pub ip_in_unknown_protos: u32,
+   pub ip_in_discards: u32,
+   pub ip_in_delivers: u32,
+   pub ip_out_requests: u32,
+   pub ip_out_discards: u32,
+   pub ip_out_no_routes: u32,
+   pub ip_reasm_timeout: u32,
+   pub ip_reasm_reqds: u32,
+   pub ip_reasm_oks: u32,
+   pub ip_reasm_fails: u32,
+   pub ip_frag_oks: u32,
+   pub ip_frag_fails: u32,
+   pub ip_frag_creates: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Mib2IcmpGroup {
+   pub icmp_in_msgs: u32,
+   pub icmp_in_errors: u32,
+   pub icmp_in_dest_unreachs: u32,
+   pub icmp_in_time_excds: u32,
+   pub icmp_in_param_probs: u32,
+   pub icmp_in_src_quenchs: u32,
+   pub icmp_in_redirects: u32,
+   pub icmp_in_echos: u32,
+   pub icmp_in_echo_reps: u32,
+   pub icmp_in_timestamps: u32,
+   pub icmp_in_addr_masks: u32,
+   pub icmp_in_addr_mask_reps: u32,
+   pub icmp_out_msgs: u32,
+   pub icmp_out_errors: u32,
+   pub icmp_out_dest_unreachs: u32,
+   pub icmp_out_time_excds: u32,
+   pub icmp_out_param_probs: u32,
+   pub icmp_out_src_quenchs: u32,
+   pub icmp_out_redirects: u32,
+   pub icmp_out_echos: u32,
+   pub icmp_out_echo_reps: u32,
+   pub icmp_out_timestamps: u32,
+   pub icmp_out_timestamp_reps: u32,
+   pub icmp_out_addr_masks: u32,
+   pub icmp_out_addr_mask_reps: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Mib2TcpGroup {
+   pub tcp_rto_algorithm: u32,
+   pub tcp_rto_min: u32,
+   pub tcp_rto_max: u32,
+   pub tcp_max_conn: u32,
+   pub tcp_active_opens: u32,
+   pub tcp_passive_opens: u32,
+   pub tcp_attempt_fails: u32,
+   pub tcp_estab_resets: u32,
+   pub tcp_curr_estab: u32,
+   pub tcp_in_segs: u32,
+   pub tcp_out_segs: u32,
+   pub tcp_retrans_segs: u32,
+   pub tcp_in_errs: u32,
+   pub tcp_out_rsts: u32,
+   pub tcp_in_csum_errors: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Mib2UdpGroup {
+   pub udp_in_datagrams: u32,
+   pub udp_no_ports: u32,
+   pub udp_in_errors: u32,
+   pub udp_out_datagrams: u32,
+   pub udp_rcvbuf_errors: u32,
+   pub udp_sndbuf_errors: u32,
+   pub udp_in_csum_errors: u32,
+}
+}
+
+// The virtual structures below describe a single virtual machine. The machine they belong to is
+// the data source of the enclosing counter sample, see CounterSample::data_source.
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct VirtNode {
+   pub mhz: u32,         /* expected CPU frequency */
+   pub cpus: u32,        /* the number of active CPUs */
+   pub memory: u64,      /* memory size in bytes */
+   pub memory_free: u64, /* unassigned memory in bytes */
+   pub num_domains: u32, /* number of active domains */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct VirtCpu {
+   pub state: u32,        /* virtDomainState */
+   pub cpu_time: u32,     /* the CPU time used (ms) */
+   pub nr_virt_cpu: u32,  /* number of virtual CPUs for the domain */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct VirtMemory {
+   pub memory: u64,     /* memory in bytes used by domain */
+   pub max_memory: u64, /* memory in bytes allowed */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct VirtDiskIo {
+   pub capacity: u64,   /* logical size in bytes */
+   pub allocation: u64, /* current allocation in bytes */
+   pub available: u64,  /* remaining free bytes */
+   pub rd_req: u32,     /* number of read requests */
+   pub rd_bytes: u64,   /* number of read bytes */
+   pub wr_req: u32,     /* number of write requests */
+   pub wr_bytes: u64,   /* number of written bytes */
+   pub errs: u32,       /* read/write errors */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct VirtNetIo {
+   pub rx_bytes: u64,   /* total bytes received */
+   pub rx_packets: u32, /* total packets received */
+   pub rx_errs: u32,    /* total receive errors */
+   pub rx_drop: u32,    /* total receive drops */
+   pub tx_bytes: u64,   /* total bytes transmitted */
+   pub tx_packets: u32, /* total packets transmitted */
+   pub tx_errs: u32,    /* total transmit errors */
+   pub tx_drop: u32,    /* total transmit drops */
+}
+}
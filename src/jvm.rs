@@ -0,0 +1,35 @@
+//! JVM holds the Java virtual machine counter structures, as exported by jmx-sflow-agent.
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct JvmRuntime {
+   pub vm_name: String,    /* vm name */
+   pub vm_vendor: String,  /* the vendor for the JVM */
+   pub vm_version: String, /* the version for the JVM */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Jmx {
+   pub heap_initial: u64,       /* initial heap memory requested */
+   pub heap_used: u64,          /* current heap memory usage */
+   pub heap_committed: u64,     /* heap memory currently committed */
+   pub heap_max: u64,           /* max heap space */
+   pub non_heap_initial: u64,   /* initial non heap memory */
+   pub non_heap_used: u64,      /* current non heap memory usage */
+   pub non_heap_committed: u64, /* committed non heap memory */
+   pub non_heap_max: u64,       /* max non heap space */
+   pub gc_count: u32,           /* total number of collections that have occurred */
+   pub gc_time: u32,            /* approximate accumulated collection elapsed time in ms */
+   pub classes_loaded: u32,     /* number of classes currently loaded in the vm */
+   pub classes_total: u32,      /* total number of classes loaded since vm started */
+   pub classes_unloaded: u32,   /* total number of classes unloaded since vm started */
+   pub compilation_time: u32,   /* total accumulated time spent in compilation (in ms) */
+   pub thread_num_live: u32,    /* current number of live threads */
+   pub thread_num_daemon: u32,  /* current number of live daemon threads */
+   pub thread_num_started: u32, /* total threads started since vm started */
+   pub fd_open_count: u32,      /* number of open file descriptors */
+   pub fd_max_count: u32,       /* max number of file descriptors */
+}
+}
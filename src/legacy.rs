@@ -0,0 +1,216 @@
+//! Legacy is responsible for decoding sFlow version 2 and 4 datagrams. These versions predate the
+//! self describing record format of version 5, so every sample and record is a tagged union with
+//! no length field. Anything that cannot be decoded is therefore fatal for the rest of the
+//! datagram.
+//!
+//! The decoded samples and records are mapped onto the version 5 types so callers can treat every
+//! datagram the same way.
+
+// Local Imports
+use counter_records::{CounterRecord, Ethernet, GenericInterface, TokenRing, Vg, Vlan};
+use datagram::Datagram;
+use as_path::{AsPathSegment, AsPathSegmentKind};
+use error::{Error, Result};
+use header_protocol::HeaderProtocol;
+use flow_records::{ExtendedGateway, ExtendedRouter, ExtendedSwitch, ExtendedUrl, ExtendedUser,
+                   FlowRecord, SampledHeader, SampledIpv4, SampledIpv6};
+use sample::{CounterSample, FlowSample, SampleRecord};
+use types::ReadSeeker;
+use utils::{Decodeable, ReadBytesLocal};
+
+// Std Lib Imports
+use std::io;
+
+pub fn read_datagram(stream: &mut ReadSeeker, version: u32) -> Result<Datagram> {
+    let agent_address = try!(Decodeable::read_and_decode(stream));
+    let sequence_number = try!(stream.be_read_u32());
+    let uptime = try!(stream.be_read_u32());
+
+    let count = try!(stream.be_read_u32());
+    let mut samples: Vec<SampleRecord> = Vec::new();
+
+    for _ in 0..count {
+        let format = try!(stream.be_read_u32());
+
+        match format {
+            1 => {
+                let fs = try!(read_flow_sample(stream, version));
+                samples.push(SampleRecord::FlowSample(fs));
+            }
+            2 => {
+                let cs = try!(read_counter_sample(stream));
+                samples.push(SampleRecord::CounterSample(cs));
+            }
+            _ => return Err(invalid_data(format!("Unknown v{0} sample type {1}", version, format))),
+        }
+    }
+
+    Ok(Datagram {
+        sflow_version: version,
+        agent_address,
+        sub_agent_id: 0,
+        sequence_number,
+        uptime,
+        sample_record: samples,
+    })
+}
+
+fn read_flow_sample(stream: &mut ReadSeeker, version: u32) -> Result<FlowSample> {
+    let mut fs = FlowSample {
+        sequence_number: try!(stream.be_read_u32()),
+        sflow_data_source: try!(stream.be_read_u32()),
+        sampling_rate: try!(stream.be_read_u32()),
+        sample_pool: try!(stream.be_read_u32()),
+        drops: try!(stream.be_read_u32()),
+        input_id: try!(stream.be_read_u32()),
+        output_id: try!(stream.be_read_u32()),
+        flow_records: Vec::new(),
+    };
+
+    fs.flow_records.push(try!(read_packet_data(stream, version)));
+
+    let count = try!(stream.be_read_u32());
+    for _ in 0..count {
+        fs.flow_records.push(try!(read_extended_data(stream, version)));
+    }
+
+    Ok(fs)
+}
+
+fn read_packet_data(stream: &mut ReadSeeker, version: u32) -> Result<FlowRecord> {
+    let format = try!(stream.be_read_u32());
+
+    match format {
+        1 => {
+            // Version 4 headers have no stripped field.
+            let e = SampledHeader {
+                protocol: try!(HeaderProtocol::read_and_decode(stream)),
+                frame_length: try!(stream.be_read_u32()),
+                stripped: 0,
+                header: try!(Decodeable::read_and_decode(stream)),
+            };
+            Ok(FlowRecord::SampledHeader(e))
+        }
+        2 => {
+            let e = try!(SampledIpv4::read_and_decode(stream));
+            Ok(FlowRecord::SampledIpv4(e))
+        }
+        3 => {
+            let e = try!(SampledIpv6::read_and_decode(stream));
+            Ok(FlowRecord::SampledIpv6(e))
+        }
+        _ => Err(invalid_data(format!("Unknown v{0} packet data type {1}", version, format))),
+    }
+}
+
+fn read_extended_data(stream: &mut ReadSeeker, version: u32) -> Result<FlowRecord> {
+    let format = try!(stream.be_read_u32());
+
+    match format {
+        1 => {
+            let e = try!(ExtendedSwitch::read_and_decode(stream));
+            Ok(FlowRecord::ExtendedSwitch(e))
+        }
+        2 => {
+            let e = try!(ExtendedRouter::read_and_decode(stream));
+            Ok(FlowRecord::ExtendedRouter(e))
+        }
+        3 => {
+            let e = try!(read_extended_gateway(stream, version));
+            Ok(FlowRecord::ExtendedGateway(e))
+        }
+        4 => {
+            // Version 4 users have no charset.
+            let e = ExtendedUser {
+                src_charset: 0,
+                src_user: try!(Decodeable::read_and_decode(stream)),
+                dst_charset: 0,
+                dst_user: try!(Decodeable::read_and_decode(stream)),
+            };
+            Ok(FlowRecord::ExtendedUser(e))
+        }
+        5 => {
+            // Version 4 urls have no host field.
+            let e = ExtendedUrl {
+                directoin: try!(stream.be_read_u32()),
+                url: try!(Decodeable::read_and_decode(stream)),
+                host: String::new(),
+            };
+            Ok(FlowRecord::ExtendedUrl(e))
+        }
+        _ => Err(invalid_data(format!("Unknown v{0} extended data type {1}", version, format))),
+    }
+}
+
+fn read_extended_gateway(stream: &mut ReadSeeker, version: u32) -> Result<ExtendedGateway> {
+    let mut e = ExtendedGateway {
+        asn: try!(stream.be_read_u32()),
+        src_as: try!(stream.be_read_u32()),
+        src_peer_as: try!(stream.be_read_u32()),
+        ..Default::default()
+    };
+
+    if version == 2 {
+        // Version 2 has a flat AS path and no communities or localpref.
+        let asns: Vec<u32> = try!(Decodeable::read_and_decode(stream));
+        e.dst_as_path.push(AsPathSegment {
+            kind: AsPathSegmentKind::AsSequence,
+            asns,
+        });
+    } else {
+        e.dst_as_path = try!(Decodeable::read_and_decode(stream));
+        e.communities = try!(Decodeable::read_and_decode(stream));
+        e.localpref = try!(stream.be_read_u32());
+    }
+
+    Ok(e)
+}
+
+fn read_counter_sample(stream: &mut ReadSeeker) -> Result<CounterSample> {
+    let mut cs = CounterSample {
+        sequence_number: try!(stream.be_read_u32()),
+        sflow_data_source: try!(stream.be_read_u32()),
+        counter_records: Vec::new(),
+    };
+
+    // The sampling interval has no counterpart in version 5.
+    let _sampling_interval = try!(stream.be_read_u32());
+    let format = try!(stream.be_read_u32());
+    if format == 0 || format > 7 {
+        return Err(invalid_data(format!("Unknown counters type {}", format)));
+    }
+
+    // Every counter type other than VLAN starts with the generic interface counters.
+    if format != 7 {
+        let g = try!(GenericInterface::read_and_decode(stream));
+        cs.counter_records.push(CounterRecord::GenericInterface(g));
+    }
+
+    match format {
+        // Generic, FDDI and WAN only carry the generic interface counters.
+        1 | 4 | 6 => {}
+        2 => {
+            let e = try!(Ethernet::read_and_decode(stream));
+            cs.counter_records.push(CounterRecord::Ethernet(e));
+        }
+        3 => {
+            let e = try!(TokenRing::read_and_decode(stream));
+            cs.counter_records.push(CounterRecord::TokenRing(e));
+        }
+        5 => {
+            let e = try!(Vg::read_and_decode(stream));
+            cs.counter_records.push(CounterRecord::Vg(e));
+        }
+        // VLAN, the only remaining type.
+        _ => {
+            let e = try!(Vlan::read_and_decode(stream));
+            cs.counter_records.push(CounterRecord::Vlan(e));
+        }
+    }
+
+    Ok(cs)
+}
+
+fn invalid_data(s: String) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, s))
+}
@@ -6,11 +6,27 @@ mod utils;
 mod sample;
 mod types;
 mod flow_records;
+mod counter_records;
 mod error;
 mod ipaddress;
-mod dst_as_path;
+mod macaddress;
+mod as_path;
 mod datagram;
+mod legacy;
 mod community;
+mod wireless;
+mod host;
+mod application;
+mod jvm;
+mod drop_reason;
+mod optical;
+mod raw;
+mod registry;
+mod broadcom;
+mod nvml;
+mod environmental;
+mod dissect;
+mod header_protocol;
 
 #[cfg(test)]
 mod test;
@@ -22,10 +38,29 @@ extern crate rustc_serialize;
 
 // Public API
 pub use utils::Decodeable;
-pub use types::ReadSeeker;
+pub use types::{split_data_format, DataSource, ReadSeeker};
 pub use error::Error;
 pub use datagram::Datagram;
-pub use sample::{FlowSample, SampleRecord};
+pub use sample::{FlowSample, CounterSample, ExpandedFlowSample, ExpandedCounterSample,
+                 DiscardSample, SampleRecord};
 pub use ipaddress::IPAddress;
+pub use macaddress::MACAddress;
 pub use flow_records::*;
-pub use community::Community;
+pub use counter_records::*;
+pub use community::{Community, WellKnownCommunity};
+pub use as_path::{AsPathSegment, AsPathSegmentKind};
+pub use wireless::*;
+pub use host::*;
+pub use application::*;
+pub use jvm::*;
+pub use drop_reason::DropReason;
+pub use optical::*;
+pub use raw::{RawSample, RawRecord};
+pub use broadcom::*;
+pub use nvml::*;
+pub use environmental::*;
+pub use header_protocol::HeaderProtocol;
+pub use dissect::{Dissection, Layer, EthernetHeader, VlanTag, MplsLabel, Ipv4Header, Ipv6Header,
+                  TcpHeader, UdpHeader, IcmpHeader};
+pub use registry::{register_flow_decoder, register_counter_decoder, register_flow_record,
+                   register_counter_record, CustomDecoder, CustomRecord};
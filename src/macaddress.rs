@@ -0,0 +1,37 @@
+use std::fmt;
+
+use types::ReadSeeker;
+use error::Error;
+use utils::Decodeable;
+
+/// MACAddress is a 48 bit hardware address. On the wire it is an XDR fixed length opaque, so the
+/// six address bytes are followed by two bytes of padding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MACAddress(pub [u8; 6]);
+
+impl fmt::Display for MACAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let b = &self.0;
+        write!(f,
+               "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+               b[0],
+               b[1],
+               b[2],
+               b[3],
+               b[4],
+               b[5])
+    }
+}
+
+impl Decodeable for MACAddress {
+    fn read_and_decode(stream: &mut ReadSeeker) -> Result<MACAddress, Error> {
+        let mut b: [u8; 6] = [0; 6];
+        try!(stream.read_exact(&mut b));
+
+        // Read the padding rather than seeking past it, so a truncated address is an error.
+        let mut padding: [u8; 2] = [0; 2];
+        try!(stream.read_exact(&mut padding));
+
+        Ok(MACAddress(b))
+    }
+}
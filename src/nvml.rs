@@ -0,0 +1,23 @@
+//! NVML holds the NVIDIA GPU counter structure exported by the hsflowd NVML module, enterprise
+//! 5703.
+
+/// NVIDIA is NVIDIA's SMI Private Enterprise Number.
+pub const NVIDIA: u32 = 5703;
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct NvidiaGpu {
+   pub device_count: u32, /* see nvmlGetDeviceCount */
+   pub processes: u32,    /* see nvmlDeviceGetComputeRunningProcesses */
+   pub gpu_time: u32,     /* total milliseconds in which one or more kernels was executing on GPU,
+                             sum across all devices */
+   pub mem_time: u32,     /* total milliseconds during which global device memory was being
+                             read/written, sum across all devices */
+   pub mem_total: u64,    /* sum of framebuffer memory across devices, see nvmlDeviceGetMemoryInfo */
+   pub mem_free: u64,     /* sum of free framebuffer memory across devices */
+   pub ecc_errors: u32,   /* sum of volatile ECC errors across devices */
+   pub energy: u32,       /* sum of millijoules across devices, see nvmlDeviceGetPowerUsage */
+   pub temperature: u32,  /* max temperature in degrees Celsius across devices */
+   pub fan_speed: u32,    /* mean fan speed in percent across devices */
+}
+}
@@ -0,0 +1,68 @@
+//! Optical holds the SFP/QSFP transceiver counter structure. Values are reported as integers in
+//! thousandths of the natural unit, helpers are provided to convert them to floating point.
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Sfp {
+   pub module_id: u32,             /* module identifier */
+   pub module_num_lanes: u32,      /* total number of lanes in module */
+   pub module_supply_voltage: u32, /* millivolts */
+   pub module_temperature: i32,    /* signed, thousandths of a degree Celsius */
+   pub lanes: Vec<SfpLane>,        /* lanes reported, which may be fewer than module_num_lanes */
+}
+}
+
+impl Sfp {
+    /// supply_voltage returns the module supply voltage in volts.
+    pub fn supply_voltage(&self) -> f64 {
+        self.module_supply_voltage as f64 / 1000.0
+    }
+
+    /// temperature returns the module temperature in degrees Celsius.
+    pub fn temperature(&self) -> f64 {
+        self.module_temperature as f64 / 1000.0
+    }
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct SfpLane {
+   pub lane_index: u32,      /* index of lane in module, starting from 1 */
+   pub tx_bias_current: u32, /* microamps */
+   pub tx_power: u32,        /* microwatts */
+   pub tx_power_min: u32,    /* microwatts */
+   pub tx_power_max: u32,    /* microwatts */
+   pub tx_wavelength: u32,   /* nanometers */
+   pub rx_power: u32,        /* microwatts */
+   pub rx_power_min: u32,    /* microwatts */
+   pub rx_power_max: u32,    /* microwatts */
+   pub rx_wavelength: u32,   /* nanometers */
+}
+}
+
+impl SfpLane {
+    /// tx_bias_current_ma returns the transmit bias current in milliamps.
+    pub fn tx_bias_current_ma(&self) -> f64 {
+        self.tx_bias_current as f64 / 1000.0
+    }
+
+    /// tx_power_mw returns the transmit power in milliwatts.
+    pub fn tx_power_mw(&self) -> f64 {
+        self.tx_power as f64 / 1000.0
+    }
+
+    /// rx_power_mw returns the receive power in milliwatts.
+    pub fn rx_power_mw(&self) -> f64 {
+        self.rx_power as f64 / 1000.0
+    }
+
+    /// tx_power_dbm returns the transmit power in dBm. No light gives negative infinity.
+    pub fn tx_power_dbm(&self) -> f64 {
+        10.0 * self.tx_power_mw().log10()
+    }
+
+    /// rx_power_dbm returns the receive power in dBm. No light gives negative infinity.
+    pub fn rx_power_dbm(&self) -> f64 {
+        10.0 * self.rx_power_mw().log10()
+    }
+}
@@ -0,0 +1,20 @@
+//! Raw holds samples and records this crate does not know how to decode. Rather than discarding
+//! them their bytes are kept as is, so they can be logged, forwarded or decoded later.
+
+/// RawSample is an undecoded sample. The sFlow data format is split into its 20 bit enterprise
+/// and 12 bit format.
+#[derive(Debug, Clone, Default)]
+pub struct RawSample {
+    pub enterprise: u32,
+    pub format: u32,
+    pub data: Vec<u8>,
+}
+
+/// RawRecord is an undecoded flow or counter record. The sFlow data format is split into its 20
+/// bit enterprise and 12 bit format.
+#[derive(Debug, Clone, Default)]
+pub struct RawRecord {
+    pub enterprise: u32,
+    pub format: u32,
+    pub data: Vec<u8>,
+}
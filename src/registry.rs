@@ -0,0 +1,152 @@
+//! Registry lets users of this crate decode enterprise specific structures without changing it.
+//! A decoder is registered for an (enterprise, format) pair and is used for any flow or counter
+//! record with that format which is not already decoded by this crate. The result is returned as
+//! FlowRecord::Custom or CounterRecord::Custom.
+//!
+//! The registry is process-global shared state. A decoder registered anywhere in the process,
+//! including by another library, is used by every decode on every thread, so decoders should be
+//! registered once before decoding starts.
+//!
+//! A decoder that returns an error does not fail the datagram. The record is kept as a RawRecord
+//! instead, with its bytes intact.
+
+// Local Imports
+use error::Error;
+use raw::RawRecord;
+use types::ReadSeeker;
+use utils::Decodeable;
+
+// Std Lib Imports
+use std::any::Any;
+use std::fmt;
+use std::io::Cursor;
+use std::sync::{Arc, RwLock};
+
+/// CustomDecoder decodes the body of a single record. The stream only holds that record's bytes.
+pub type CustomDecoder = fn(&mut ReadSeeker) -> Result<Arc<Any + Send + Sync>, Error>;
+
+static FLOW_DECODERS: RwLock<Vec<(u32, u32, CustomDecoder)>> = RwLock::new(Vec::new());
+static COUNTER_DECODERS: RwLock<Vec<(u32, u32, CustomDecoder)>> = RwLock::new(Vec::new());
+
+/// register_flow_decoder registers a decoder for flow records of the given enterprise and format.
+/// Registering the same pair again replaces the previous decoder.
+pub fn register_flow_decoder(enterprise: u32, format: u32, decoder: CustomDecoder) {
+    register(&FLOW_DECODERS, enterprise, format, decoder);
+}
+
+/// register_counter_decoder registers a decoder for counter records of the given enterprise and
+/// format. Registering the same pair again replaces the previous decoder.
+pub fn register_counter_decoder(enterprise: u32, format: u32, decoder: CustomDecoder) {
+    register(&COUNTER_DECODERS, enterprise, format, decoder);
+}
+
+/// register_flow_record registers T as the decoded form of flow records of the given enterprise
+/// and format.
+pub fn register_flow_record<T>(enterprise: u32, format: u32)
+    where T: Decodeable + Any + Send + Sync
+{
+    register_flow_decoder(enterprise, format, decode_as::<T>);
+}
+
+/// register_counter_record registers T as the decoded form of counter records of the given
+/// enterprise and format.
+pub fn register_counter_record<T>(enterprise: u32, format: u32)
+    where T: Decodeable + Any + Send + Sync
+{
+    register_counter_decoder(enterprise, format, decode_as::<T>);
+}
+
+pub fn flow_decoder(enterprise: u32, format: u32) -> Option<CustomDecoder> {
+    lookup(&FLOW_DECODERS, enterprise, format)
+}
+
+pub fn counter_decoder(enterprise: u32, format: u32) -> Option<CustomDecoder> {
+    lookup(&COUNTER_DECODERS, enterprise, format)
+}
+
+/// decode_unregistered gives the decoder find returns for a record's format, if any, a chance at a
+/// record this crate does not decode itself. Without a decoder, or when it fails, the record is
+/// returned raw rather than failing the datagram.
+pub fn decode_unregistered(find: fn(u32, u32) -> Option<CustomDecoder>,
+                           enterprise: u32,
+                           format: u32,
+                           data: Vec<u8>)
+                           -> Result<CustomRecord, RawRecord> {
+    if let Some(decoder) = find(enterprise, format) {
+        if let Ok(e) = CustomRecord::decode(decoder, enterprise, format, &data) {
+            return Ok(e);
+        }
+    }
+
+    Err(RawRecord {
+        enterprise,
+        format,
+        data,
+    })
+}
+
+fn register(decoders: &RwLock<Vec<(u32, u32, CustomDecoder)>>,
+            enterprise: u32,
+            format: u32,
+            decoder: CustomDecoder) {
+    let mut decoders = decoders.write().unwrap_or_else(|e| e.into_inner());
+    decoders.retain(|&(e, f, _)| (e, f) != (enterprise, format));
+    decoders.push((enterprise, format, decoder));
+}
+
+fn lookup(decoders: &RwLock<Vec<(u32, u32, CustomDecoder)>>,
+          enterprise: u32,
+          format: u32)
+          -> Option<CustomDecoder> {
+    let decoders = decoders.read().unwrap_or_else(|e| e.into_inner());
+    decoders.iter().find(|&&(e, f, _)| (e, f) == (enterprise, format)).map(|&(_, _, d)| d)
+}
+
+fn decode_as<T>(stream: &mut ReadSeeker) -> Result<Arc<Any + Send + Sync>, Error>
+    where T: Decodeable + Any + Send + Sync
+{
+    let t = try!(T::read_and_decode(stream));
+
+    Ok(Arc::new(t))
+}
+
+/// CustomRecord is a record decoded by a registered decoder. Use downcast_ref to get at the value
+/// with the type the decoder produced.
+#[derive(Clone)]
+pub struct CustomRecord {
+    pub enterprise: u32,
+    pub format: u32,
+    pub value: Arc<Any + Send + Sync>,
+}
+
+impl CustomRecord {
+    /// decode runs decoder over the body of a record. This can also be used to decode a RawRecord
+    /// kept from before a decoder was registered, or whose decoder failed.
+    pub fn decode(decoder: CustomDecoder,
+                  enterprise: u32,
+                  format: u32,
+                  data: &[u8])
+                  -> Result<CustomRecord, Error> {
+        let mut stream = Cursor::new(data);
+
+        Ok(CustomRecord {
+            enterprise,
+            format,
+            value: try!(decoder(&mut stream)),
+        })
+    }
+
+    /// downcast_ref returns the decoded value if it is a T.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref::<T>()
+    }
+}
+
+impl fmt::Debug for CustomRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "CustomRecord {{ enterprise: {}, format: {}, .. }}",
+               self.enterprise,
+               self.format)
+    }
+}
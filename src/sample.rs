@@ -1,16 +1,20 @@
 // Local Imports
 use types::*;
-use utils::ReadBytesLocal;
+use utils::{read_body, ReadBytesLocal};
 use flow_records::FlowRecord;
+use counter_records::CounterRecord;
+use drop_reason::DropReason;
+use raw::RawSample;
 use error::Result;
 
-// Std Lib Imports
-use std::io::SeekFrom;
-
 #[derive(Debug, Clone)]
 pub enum SampleRecord {
     FlowSample(FlowSample),
-    Unknown,
+    CounterSample(CounterSample),
+    ExpandedFlowSample(ExpandedFlowSample),
+    ExpandedCounterSample(ExpandedCounterSample),
+    DiscardSample(DiscardSample),
+    RawSample(RawSample),
 }
 
 add_decoder!{
@@ -48,6 +52,123 @@ pub struct FlowSample {
 }
 }
 
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct CounterSample {
+    // Incremented with each counter sample generated by this source_id.
+    pub sequence_number: u32,
+
+    // sFlowDataSource
+    pub sflow_data_source: SourceID,
+
+    // Counters polled for this source
+    pub counter_records: Vec<CounterRecord>,
+}
+}
+
+impl CounterSample {
+    /// data_source returns the entity these counters describe.
+    pub fn data_source(&self) -> DataSource {
+        DataSource::from_source_id(self.sflow_data_source)
+    }
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct ExpandedFlowSample {
+    // Incremented with each flow sample generated by this source_id.
+    pub sequence_number: u32,
+
+    // sFlowDataSource type and index, split apart so the index may use the full 32 bits.
+    pub source_id_type: u32,
+    pub source_id_index: u32,
+
+    // sFlowPacketSamplingRate
+    pub sampling_rate: u32,
+
+    // Total number of packets that could have been sampled.
+    pub sample_pool: u32,
+
+    // Number of times a packet marked to be sampled was dropped due to lack of resources.
+    pub drops: u32,
+
+    // Interface packet was received on. The format is 0 for an ifIndex, otherwise see the
+    // interface encoding of FlowSample.
+    pub input_format: u32,
+    pub input_value: u32,
+
+    // Interface packet was sent on.
+    pub output_format: u32,
+    pub output_value: u32,
+
+    // Information about a sampled packet
+    pub flow_records: Vec<FlowRecord>,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct ExpandedCounterSample {
+    // Incremented with each counter sample generated by this source_id.
+    pub sequence_number: u32,
+
+    // sFlowDataSource type and index, split apart so the index may use the full 32 bits.
+    pub source_id_type: u32,
+    pub source_id_index: u32,
+
+    // Counters polled for this source
+    pub counter_records: Vec<CounterRecord>,
+}
+}
+
+impl ExpandedCounterSample {
+    /// data_source returns the entity these counters describe.
+    pub fn data_source(&self) -> DataSource {
+        DataSource {
+            source_id_type: self.source_id_type,
+            source_id_index: self.source_id_index,
+        }
+    }
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct DiscardSample {
+    // Incremented with each discard sample generated by this source_id.
+    pub sequence_number: u32,
+
+    // sFlowDataSource type and index.
+    pub source_id_type: u32,
+    pub source_id_index: u32,
+
+    // Number of times a packet discard was detected but not reported due to lack of resources.
+    pub drops: u32,
+
+    // Interface packet was received on, 0 if unknown.
+    pub input_id: Interface,
+
+    // Interface packet would have been sent on. Bit 30 is set if the packet was discarded before
+    // an output interface was selected, 0 if unknown.
+    pub output_id: Interface,
+
+    // Reason for dropping the packet.
+    pub reason: DropReason,
+
+    // Information about the discarded packet
+    pub discard_records: Vec<FlowRecord>,
+}
+}
+
+impl DiscardSample {
+    /// data_source returns the entity that discarded the packet.
+    pub fn data_source(&self) -> DataSource {
+        DataSource {
+            source_id_type: self.source_id_type,
+            source_id_index: self.source_id_index,
+        }
+    }
+}
+
 impl ::utils::Decodeable for Vec<SampleRecord> {
     fn read_and_decode(stream: &mut ReadSeeker) -> Result<Vec<SampleRecord>> {
         // First we need to figure out how many samples there are.
@@ -55,18 +176,41 @@ impl ::utils::Decodeable for Vec<SampleRecord> {
         let mut results: Vec<SampleRecord> = Vec::new();
 
         for _ in 0..count {
-            let format = try!(stream.be_read_u32());
+            let (enterprise, format) = split_data_format(try!(stream.be_read_u32()));
             let length = try!(stream.be_read_u32());
 
-            match format {
-                1 => {
-                    let fs: FlowSample = try!(::utils::Decodeable::read_and_decode(stream));
+            let mut body = try!(read_body(stream, length));
+
+            match (enterprise, format) {
+                (0, 1) => {
+                    let fs: FlowSample = try!(::utils::Decodeable::read_and_decode(&mut body));
                     results.push(SampleRecord::FlowSample(fs));
                 }
-                // Skip unknown samples.
+                (0, 2) => {
+                    let cs: CounterSample = try!(::utils::Decodeable::read_and_decode(&mut body));
+                    results.push(SampleRecord::CounterSample(cs));
+                }
+                (0, 3) => {
+                    let fs: ExpandedFlowSample = try!(::utils::Decodeable::read_and_decode(&mut body));
+                    results.push(SampleRecord::ExpandedFlowSample(fs));
+                }
+                (0, 4) => {
+                    let cs: ExpandedCounterSample =
+                        try!(::utils::Decodeable::read_and_decode(&mut body));
+                    results.push(SampleRecord::ExpandedCounterSample(cs));
+                }
+                (0, 5) => {
+                    let ds: DiscardSample = try!(::utils::Decodeable::read_and_decode(&mut body));
+                    results.push(SampleRecord::DiscardSample(ds));
+                }
+                // Keep unknown samples undecoded.
                 _ => {
-                    results.push(SampleRecord::Unknown);
-                    try!(stream.seek(SeekFrom::Current(length as i64)));
+                    let rs = RawSample {
+                        enterprise,
+                        format,
+                        data: body.into_inner(),
+                    };
+                    results.push(SampleRecord::RawSample(rs));
                 }
             }
         }
@@ -1,8 +1,22 @@
 // Standard Imports
 use std::io::Cursor;
+use std::net::{Ipv4Addr, Ipv6Addr};
 
 // Internal Imports
 use datagram::Datagram;
+use sample::SampleRecord;
+use counter_records::CounterRecord;
+use flow_records::{FlowRecord, SampledHeader};
+use dissect::Layer;
+use header_protocol::HeaderProtocol;
+use error::Error;
+use types::DataSource;
+use drop_reason::DropReason;
+use macaddress::MACAddress;
+use ipaddress::IPAddress;
+use as_path::AsPathSegmentKind;
+use community::WellKnownCommunity;
+use registry::{register_counter_record, register_flow_record};
 
 // External Test Imports
 #[cfg(test)]
@@ -39,3 +53,1775 @@ fn test_decode_string() {
         assert_eq!(case.result, res);
     }
 }
+
+#[test]
+fn test_decode_counter_sample() {
+    let raw_test_data = concat!("00000001", // sample count
+                                "00000002", // format: counter sample
+                                "000000a8", // sample length
+                                "00000007", // sequence number
+                                "00000003", // source id
+                                "00000002", // counter record count
+                                // Generic interface counters
+                                "00000001",
+                                "00000058",
+                                "00000003", // if_index
+                                "00000006", // if_type
+                                "000000003b9aca00", // if_speed
+                                "00000001", // if_direction
+                                "00000003", // if_status
+                                "0000000100000000", // if_in_octets
+                                "00000001",
+                                "00000002",
+                                "00000003",
+                                "00000004", // if_in_discards
+                                "00000005", // if_in_errors
+                                "00000006",
+                                "0000000000000400", // if_out_octets
+                                "00000007",
+                                "00000008",
+                                "00000009",
+                                "0000000a",
+                                "0000000b",
+                                "00000000",
+                                // Ethernet counters
+                                "00000002",
+                                "00000034",
+                                "00000001",
+                                "00000002",
+                                "00000003",
+                                "00000004",
+                                "00000005",
+                                "00000006",
+                                "00000007",
+                                "00000008",
+                                "00000009",
+                                "0000000a",
+                                "0000000b",
+                                "0000000c",
+                                "0000000d");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let samples: Vec<SampleRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(samples.len(), 1);
+    let cs = match samples[0] {
+        SampleRecord::CounterSample(ref cs) => cs,
+        ref s => panic!("expected counter sample, got {:?}", s),
+    };
+    assert_eq!(cs.sequence_number, 7);
+    assert_eq!(cs.sflow_data_source, 3);
+    assert_eq!(cs.counter_records.len(), 2);
+
+    match cs.counter_records[0] {
+        CounterRecord::GenericInterface(ref g) => {
+            assert_eq!(g.if_index, 3);
+            assert_eq!(g.if_speed, 1000000000);
+            assert_eq!(g.if_status, 3);
+            assert_eq!(g.if_in_octets, 1 << 32);
+            assert_eq!(g.if_in_errors, 5);
+            assert_eq!(g.if_out_octets, 1024);
+            assert_eq!(g.if_out_errors, 11);
+        }
+        ref r => panic!("expected generic interface counters, got {:?}", r),
+    }
+    match cs.counter_records[1] {
+        CounterRecord::Ethernet(ref e) => {
+            assert_eq!(e.dot3_stats_alignment_errors, 1);
+            assert_eq!(e.dot3_stats_symbol_errors, 13);
+        }
+        ref r => panic!("expected ethernet counters, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_expanded_flow_sample() {
+    let raw_test_data = concat!("00000001", // sample count
+                                "00000003", // format: expanded flow sample
+                                "00000044", // sample length
+                                "00000001", // sequence number
+                                "00000000", // source_id_type
+                                "01000002", // source_id_index
+                                "00000400", // sampling_rate
+                                "00010000", // sample_pool
+                                "00000000", // drops
+                                "00000000", // input_format
+                                "01000002", // input_value
+                                "00000000", // output_format
+                                "01000003", // output_value
+                                "00000001", // flow record count
+                                // Extended switch
+                                "000003e9",
+                                "00000010",
+                                "0000000a",
+                                "00000001",
+                                "00000014",
+                                "00000002");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let samples: Vec<SampleRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(samples.len(), 1);
+    let fs = match samples[0] {
+        SampleRecord::ExpandedFlowSample(ref fs) => fs,
+        ref s => panic!("expected expanded flow sample, got {:?}", s),
+    };
+    assert_eq!(fs.source_id_index, 0x01000002);
+    assert_eq!(fs.input_value, 0x01000002);
+    assert_eq!(fs.output_value, 0x01000003);
+    assert_eq!(fs.flow_records.len(), 1);
+
+    match fs.flow_records[0] {
+        FlowRecord::ExtendedSwitch(ref e) => {
+            assert_eq!(e.src_vlan, 10);
+            assert_eq!(e.dst_vlan, 20);
+        }
+        ref r => panic!("expected extended switch, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_v4_datagram() {
+    let raw_test_data = concat!("00000004", // version
+                                "00000001", // agent address type
+                                "0a000001", // agent address
+                                "0000002a", // sequence number
+                                "00001000", // uptime
+                                "00000002", // sample count
+                                // Flow sample
+                                "00000001",
+                                "00000005", // sequence number
+                                "00000002", // source id
+                                "00000100", // sampling rate
+                                "00001000", // sample pool
+                                "00000000", // drops
+                                "00000002", // input
+                                "00000003", // output
+                                "00000001", // packet data: header
+                                "00000001", // protocol
+                                "00000040", // frame length
+                                "00000006", // header length
+                                "0102030405060000",
+                                "00000001", // extended data count
+                                "00000001", // extended switch
+                                "0000000a",
+                                "00000000",
+                                "00000014",
+                                "00000000",
+                                // Counter sample
+                                "00000002",
+                                "00000006", // sequence number
+                                "00000002", // source id
+                                "0000001e", // sampling interval
+                                "00000002", // counters type: ethernet
+                                "00000002", // if_index
+                                "00000006",
+                                "0000000005f5e100",
+                                "00000001",
+                                "00000003",
+                                "0000000000000001",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "0000000000000002",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000001", // dot3_stats_alignment_errors
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let d: Datagram = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(d.sflow_version, 4);
+    assert_eq!(d.sub_agent_id, 0);
+    assert_eq!(d.sequence_number, 42);
+    assert_eq!(d.sample_record.len(), 2);
+
+    match d.sample_record[0] {
+        SampleRecord::FlowSample(ref fs) => {
+            assert_eq!(fs.sampling_rate, 256);
+            assert_eq!(fs.flow_records.len(), 2);
+            match fs.flow_records[0] {
+                FlowRecord::SampledHeader(ref h) => {
+                    assert_eq!(h.protocol, HeaderProtocol::EthernetIso88023);
+                    assert_eq!(h.frame_length, 64);
+                    assert_eq!(h.stripped, 0);
+                    assert_eq!(h.header, vec![1, 2, 3, 4, 5, 6]);
+                }
+                ref r => panic!("expected sampled header, got {:?}", r),
+            }
+        }
+        ref s => panic!("expected flow sample, got {:?}", s),
+    }
+
+    match d.sample_record[1] {
+        SampleRecord::CounterSample(ref cs) => {
+            assert_eq!(cs.counter_records.len(), 2);
+            match cs.counter_records[1] {
+                CounterRecord::Ethernet(ref e) => assert_eq!(e.dot3_stats_alignment_errors, 1),
+                ref r => panic!("expected ethernet counters, got {:?}", r),
+            }
+        }
+        ref s => panic!("expected counter sample, got {:?}", s),
+    }
+}
+
+#[test]
+fn test_decode_unsupported_version() {
+    let mut data = Cursor::new("00000003".from_hex().unwrap());
+    let res: Result<Datagram, Error> = ::utils::Decodeable::read_and_decode(&mut data);
+
+    match res {
+        Err(Error::UnsupportedVersion(3)) => {}
+        r => panic!("expected unsupported version error, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_sampled_ipv4() {
+    let raw_test_data = concat!("00000001", // flow record count
+                                // Sampled ipv4
+                                "00000003",
+                                "00000020",
+                                "000005dc", // length
+                                "00000011", // protocol
+                                "0a000001", // src_ip
+                                "0a000002", // dst_ip
+                                "0000d431", // src_port
+                                "00000035", // dst_port
+                                "00000000", // tcp_flags
+                                "00000010"); // tos
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(records.len(), 1);
+    match records[0] {
+        FlowRecord::SampledIpv4(ref e) => {
+            assert_eq!(e.protocol, 17);
+            assert_eq!(e.src_ip.octets(), [10, 0, 0, 1]);
+            assert_eq!(e.dst_ip.octets(), [10, 0, 0, 2]);
+            assert_eq!(e.src_port, 54321);
+            assert_eq!(e.dst_port, 53);
+            assert_eq!(e.tos, 0x10);
+        }
+        ref r => panic!("expected sampled ipv4, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_sampled_ethernet_and_ipv6() {
+    let raw_test_data = concat!("00000002", // flow record count
+                                // Sampled ethernet
+                                "00000002",
+                                "00000018",
+                                "000005ee", // length
+                                "001122334455", "0000", // src_mac
+                                "66778899aabb", "0000", // dst_mac
+                                "000086dd", // eth_type
+                                // Sampled ipv6
+                                "00000004",
+                                "00000038",
+                                "000005dc", // length
+                                "00000006", // protocol
+                                "20010db8000000000000000000000001", // src_ip
+                                "20010db8000000000000000000000002", // dst_ip
+                                "0000c350", // src_port
+                                "000001bb", // dst_port
+                                "00000012", // tcp_flags
+                                "00000000"); // priority
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(records.len(), 2);
+    match records[0] {
+        FlowRecord::SampledEthernet(ref e) => {
+            assert_eq!(e.src_mac.to_string(), "00:11:22:33:44:55");
+            assert_eq!(e.dst_mac.to_string(), "66:77:88:99:aa:bb");
+            assert_eq!(e.eth_type, 0x86dd);
+        }
+        ref r => panic!("expected sampled ethernet, got {:?}", r),
+    }
+    match records[1] {
+        FlowRecord::SampledIpv6(ref e) => {
+            assert_eq!(e.src_ip, "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+            assert_eq!(e.dst_ip, "2001:db8::2".parse::<Ipv6Addr>().unwrap());
+            assert_eq!(e.dst_port, 443);
+        }
+        ref r => panic!("expected sampled ipv6, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_80211_aggregation() {
+    let raw_test_data = concat!("00000001", // flow record count
+                                // Extended 802.11 aggregation
+                                "000003f8",
+                                "00000038",
+                                "00000002", // pdu count
+                                // First pdu
+                                "00000001", // flow record count
+                                "000003e9", // extended switch
+                                "00000010",
+                                "0000000a",
+                                "00000000",
+                                "0000000b",
+                                "00000000",
+                                // Second pdu
+                                "00000001", // flow record count
+                                "000003f5", // extended 802.11 payload
+                                "0000000c",
+                                "000fac04", // cipher_suite
+                                "00000003", // data length
+                                "aabbcc00");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(records.len(), 1);
+    let agg = match records[0] {
+        FlowRecord::Extended80211Aggregation(ref agg) => agg,
+        ref r => panic!("expected 802.11 aggregation, got {:?}", r),
+    };
+    assert_eq!(agg.pdus.len(), 2);
+    match agg.pdus[0].flow_records[0] {
+        FlowRecord::ExtendedSwitch(ref e) => assert_eq!(e.dst_vlan, 11),
+        ref r => panic!("expected extended switch, got {:?}", r),
+    }
+    match agg.pdus[1].flow_records[0] {
+        FlowRecord::Extended80211Payload(ref e) => {
+            assert_eq!(e.cipher_suite, 0x000fac04);
+            assert_eq!(e.data, vec![0xaa, 0xbb, 0xcc]);
+        }
+        ref r => panic!("expected 802.11 payload, got {:?}", r),
+    }
+    assert_eq!(data.position(), data.get_ref().len() as u64);
+}
+
+#[test]
+fn test_decode_nested_80211_aggregation() {
+    // Wrap an extended switch record in enough aggregations to overflow the stack if each one
+    // were recursed into.
+    let mut record = "000003e9000000100000000a000000000000000b00000000".from_hex().unwrap();
+    for _ in 0..1000 {
+        let mut outer = format!("000003f8{:08x}0000000100000001", record.len() + 8)
+            .from_hex()
+            .unwrap();
+        outer.extend(record);
+        record = outer;
+    }
+
+    let mut raw_test_data = "00000001".from_hex().unwrap(); // flow record count
+    raw_test_data.extend(record);
+
+    let mut data = Cursor::new(raw_test_data);
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    let agg = match records[0] {
+        FlowRecord::Extended80211Aggregation(ref agg) => agg,
+        ref r => panic!("expected 802.11 aggregation, got {:?}", r),
+    };
+    match agg.pdus[0].flow_records[0] {
+        FlowRecord::RawRecord(ref r) => assert_eq!((r.enterprise, r.format), (0, 1016)),
+        ref r => panic!("expected raw record, got {:?}", r),
+    }
+    assert_eq!(data.position(), data.get_ref().len() as u64);
+}
+
+#[test]
+fn test_decode_host_counters() {
+    let raw_test_data = concat!("00000003", // counter record count
+                                // Host adapters
+                                "000007d1",
+                                "00000014",
+                                "00000001", // adapter count
+                                "00000002", // if_index
+                                "00000001", // mac address count
+                                "525400123456", "0000",
+                                // Host descr
+                                "000007d0",
+                                "0000002c",
+                                "00000004", "77656231", // hostname
+                                "000102030405060708090a0b0c0d0e0f", // uuid
+                                "00000007", // machine_type
+                                "00000002", // os_name
+                                "00000006", "342e31392e300000", // os_release
+                                // Host cpu
+                                "000007d3",
+                                "00000050",
+                                "3fc00000", // load_one
+                                "3f800000", // load_five
+                                "3f000000", // load_fifteen
+                                "00000001",
+                                "000000c8",
+                                "00000008", // cpu_num
+                                "00000bb8", // cpu_speed
+                                "00015180", // uptime
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<CounterRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(records.len(), 3);
+    match records[0] {
+        CounterRecord::HostAdapters(ref e) => {
+            assert_eq!(e.adapters.len(), 1);
+            assert_eq!(e.adapters[0].if_index, 2);
+            assert_eq!(e.adapters[0].mac_addresses[0].to_string(), "52:54:00:12:34:56");
+        }
+        ref r => panic!("expected host adapters, got {:?}", r),
+    }
+    match records[1] {
+        CounterRecord::HostDescr(ref e) => {
+            assert_eq!(e.hostname, "web1");
+            assert_eq!(e.uuid[15], 0x0f);
+            assert_eq!(e.machine_type, 7);
+            assert_eq!(e.os_release, "4.19.0");
+        }
+        ref r => panic!("expected host descr, got {:?}", r),
+    }
+    match records[2] {
+        CounterRecord::HostCpu(ref e) => {
+            assert_eq!(e.load_one, 1.5);
+            assert_eq!(e.load_fifteen, 0.5);
+            assert_eq!(e.cpu_num, 8);
+            assert_eq!(e.uptime, 86400);
+        }
+        ref r => panic!("expected host cpu, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_virtual_counter_sample() {
+    let raw_test_data = concat!("00000001", // sample count
+                                "00000002", // format: counter sample
+                                "00000020", // sample length
+                                "00000009", // sequence number
+                                "03000005", // source id: logical entity 5
+                                "00000001", // counter record count
+                                // Virt cpu
+                                "00000835",
+                                "0000000c",
+                                "00000001", // state
+                                "000003e8", // cpu_time
+                                "00000002"); // nr_virt_cpu
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let samples: Vec<SampleRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    let cs = match samples[0] {
+        SampleRecord::CounterSample(ref cs) => cs,
+        ref s => panic!("expected counter sample, got {:?}", s),
+    };
+    assert_eq!(cs.data_source(),
+               DataSource {
+                   source_id_type: 3,
+                   source_id_index: 5,
+               });
+    match cs.counter_records[0] {
+        CounterRecord::VirtCpu(ref e) => {
+            assert_eq!(e.cpu_time, 1000);
+            assert_eq!(e.nr_virt_cpu, 2);
+        }
+        ref r => panic!("expected virt cpu, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_http_request() {
+    let raw_test_data = concat!("00000001", // flow record count
+                                // Http request
+                                "0000089e",
+                                "00000060",
+                                "00000002", // method: GET
+                                "000003e9", // protocol: HTTP/1.1
+                                "00000006", "2f696e646578", "0000", // uri
+                                "0000000b", "6578616d706c652e636f6d", "00", // host
+                                "00000000", // referer
+                                "00000004", "6375726c", // useragent
+                                "00000000", // xff
+                                "00000000", // authuser
+                                "00000009", "746578742f68746d6c", "000000", // mime_type
+                                "0000000000000000", // req_bytes
+                                "0000000000001000", // resp_bytes
+                                "000001f4", // duration_us
+                                "000000c8"); // status
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    match records[0] {
+        FlowRecord::HttpRequest(ref e) => {
+            assert_eq!(e.method, 2);
+            assert_eq!(e.uri, "/index");
+            assert_eq!(e.host, "example.com");
+            assert_eq!(e.useragent, "curl");
+            assert_eq!(e.mime_type, "text/html");
+            assert_eq!(e.resp_bytes, 4096);
+            assert_eq!(e.duration_us, 500);
+            assert_eq!(e.status, 200);
+        }
+        ref r => panic!("expected http request, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_tunnel_records() {
+    let raw_test_data = concat!("00000002", // flow record count
+                                // Extended ipv4 tunnel egress
+                                "000003ff",
+                                "00000020",
+                                "00000032", // length
+                                "00000011", // protocol
+                                "c0000201", // src_ip
+                                "c0000202", // dst_ip
+                                "0000c000", // src_port
+                                "000012b5", // dst_port
+                                "00000000", // tcp_flags
+                                "00000000", // tos
+                                // Extended vni ingress
+                                "00000406",
+                                "00000004",
+                                "00002710"); // vni
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(records.len(), 2);
+    match records[0] {
+        FlowRecord::ExtendedIpv4TunnelEgress(ref e) => {
+            assert_eq!(e.src_ip, Ipv4Addr::new(192, 0, 2, 1));
+            assert_eq!(e.dst_ip, Ipv4Addr::new(192, 0, 2, 2));
+            assert_eq!(e.dst_port, 4789);
+        }
+        ref r => panic!("expected ipv4 tunnel egress, got {:?}", r),
+    }
+    match records[1] {
+        FlowRecord::ExtendedVniIngress(ref e) => assert_eq!(e.vni, 10000),
+        ref r => panic!("expected vni ingress, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_discard_sample() {
+    let raw_test_data = concat!("00000002", // sample count
+                                "00000005", // format: discard sample
+                                "0000003c", // sample length
+                                "00000001", // sequence number
+                                "00000000", // source_id_type
+                                "00000004", // source_id_index
+                                "00000000", // drops
+                                "00000004", // input_id
+                                "40000000", // output_id
+                                "00000103", // reason: no_buffer_space
+                                "00000001", // discard record count
+                                // Extended hw trap
+                                "00000411",
+                                "00000014",
+                                "00000002", "6c320000", // group
+                                "00000008", "6d74755f64726f70", // trap
+                                "00000005", // format: discard sample
+                                "00000020", // sample length
+                                "00000002", // sequence number
+                                "00000000", // source_id_type
+                                "00000004", // source_id_index
+                                "00000000", // drops
+                                "00000004", // input_id
+                                "00000005", // output_id
+                                "000003e7", // reason: not a known code
+                                "00000000"); // discard record count
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let samples: Vec<SampleRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(samples.len(), 2);
+    match samples[0] {
+        SampleRecord::DiscardSample(ref ds) => {
+            assert_eq!(ds.data_source(),
+                       DataSource {
+                           source_id_type: 0,
+                           source_id_index: 4,
+                       });
+            assert_eq!(ds.reason, DropReason::NoBufferSpace);
+            assert_eq!(ds.reason.code(), 259);
+            match ds.discard_records[0] {
+                FlowRecord::ExtendedHwTrap(ref e) => {
+                    assert_eq!(e.group, "l2");
+                    assert_eq!(e.trap, "mtu_drop");
+                }
+                ref r => panic!("expected extended hw trap, got {:?}", r),
+            }
+        }
+        ref s => panic!("expected discard sample, got {:?}", s),
+    }
+    match samples[1] {
+        SampleRecord::DiscardSample(ref ds) => {
+            assert_eq!(ds.reason, DropReason::Other(999));
+            assert_eq!(ds.reason.code(), 999);
+        }
+        ref s => panic!("expected discard sample, got {:?}", s),
+    }
+
+    assert_eq!(DropReason::from_code(293), DropReason::SrcMacIsDmac);
+    assert_eq!(DropReason::from_code(303), DropReason::SplitHorizon);
+    assert_eq!(DropReason::from_code(304), DropReason::Other(304));
+}
+
+#[test]
+fn test_decode_lag_port_stats() {
+    let raw_test_data = concat!("00000001", // counter record count
+                                // Lag port stats
+                                "00000007",
+                                "00000038",
+                                "0c0000000001", "0000", // actor system id
+                                "0c0000000002", "0000", // partner system id
+                                "00000065", // attached agg id
+                                "3d3d3d3d", // port state
+                                "00000010", // lacpdus rx
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000011", // lacpdus tx
+                                "00000000",
+                                "00000000");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<CounterRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    match records[0] {
+        CounterRecord::LagPortStats(ref e) => {
+            assert_eq!(e.dot3ad_agg_port_actor_system_id,
+                       MACAddress([0x0c, 0, 0, 0, 0, 1]));
+            assert_eq!(e.dot3ad_agg_port_partner_system_id.to_string(),
+                       "0c:00:00:00:00:02");
+            assert_eq!(e.dot3ad_agg_port_attached_agg_id, 101);
+            assert_eq!(e.dot3ad_agg_port_state.partner_oper, 0x3d);
+            assert_eq!(e.dot3ad_agg_port_stats_lacpdus_rx, 16);
+            assert_eq!(e.dot3ad_agg_port_stats_lacpdus_tx, 17);
+        }
+        ref r => panic!("expected lag port stats, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_sfp() {
+    let raw_test_data = concat!("00000001", // counter record count
+                                // Sfp
+                                "0000000a",
+                                "00000064",
+                                "00000001", // module_id
+                                "00000004", // module_num_lanes
+                                "00000ce4", // module_supply_voltage
+                                "ffffd8f0", // module_temperature
+                                "00000002", // lane count
+                                "00000001", // lane_index
+                                "00001b58", // tx_bias_current
+                                "000003e8", // tx_power
+                                "00000000",
+                                "00000000",
+                                "0000051e", // tx_wavelength
+                                "000001f4", // rx_power
+                                "00000000",
+                                "00000000",
+                                "0000051e", // rx_wavelength
+                                "00000002", // lane_index
+                                "00001b58",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "0000051e",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "0000051e");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<CounterRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    match records[0] {
+        CounterRecord::Sfp(ref e) => {
+            assert_eq!(e.supply_voltage(), 3.3);
+            assert_eq!(e.temperature(), -10.0);
+            assert_eq!(e.lanes.len(), 2);
+            assert_eq!(e.lanes[0].tx_bias_current_ma(), 7.0);
+            assert_eq!(e.lanes[0].tx_power_mw(), 1.0);
+            assert_eq!(e.lanes[0].tx_power_dbm(), 0.0);
+            assert_eq!(e.lanes[0].rx_power_mw(), 0.5);
+            assert_eq!(e.lanes[1].lane_index, 2);
+            assert_eq!(e.lanes[1].rx_power_dbm(), f64::NEG_INFINITY);
+        }
+        ref r => panic!("expected sfp, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_socket_records() {
+    let raw_test_data = concat!("00000002", // flow record count
+                                // Extended socket ipv4
+                                "00000834",
+                                "00000014",
+                                "00000006", // protocol
+                                "0a000001", // local_ip
+                                "0a000002", // remote_ip
+                                "00000050", // local_port
+                                "0000d431", // remote_port
+                                // Extended proxy socket ipv6
+                                "00000837",
+                                "0000002c",
+                                "00000006", // protocol
+                                "00000000000000000000000000000001", // local_ip
+                                "20010db8000000000000000000000002", // remote_ip
+                                "0000d432", // local_port
+                                "00001f90"); // remote_port
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    match records[0] {
+        FlowRecord::ExtendedSocketIpv4(ref e) => {
+            assert_eq!(e.local_ip, Ipv4Addr::new(10, 0, 0, 1));
+            assert_eq!(e.local_port, 80);
+            assert_eq!(e.remote_port, 54321);
+        }
+        ref r => panic!("expected extended socket ipv4, got {:?}", r),
+    }
+    match records[1] {
+        FlowRecord::ExtendedProxySocketIpv6(ref e) => {
+            assert_eq!(e.local_ip, Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+            assert_eq!(e.remote_port, 8080);
+        }
+        ref r => panic!("expected extended proxy socket ipv6, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_extended_gateway() {
+    let raw_test_data = concat!("00000001", // flow record count
+                                // Extended gateway
+                                "000003eb",
+                                "00000048",
+                                "00000001", "c0000201", // next_hop
+                                "0000fde8", // asn
+                                "00000000", // src_as
+                                "00000000", // src_peer_as
+                                "00000002", // segment count
+                                "00000002", // AS_SEQUENCE
+                                "00000002",
+                                "0000fde9",
+                                "00030d40",
+                                "00000001", // AS_SET
+                                "00000002",
+                                "0000fdea",
+                                "0000fdeb",
+                                "00000002", // community count
+                                "fde8029a", // 65000:666
+                                "ffffff01", // NO_EXPORT
+                                "00000064"); // localpref
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    let gw = match records[0] {
+        FlowRecord::ExtendedGateway(ref gw) => gw,
+        ref r => panic!("expected extended gateway, got {:?}", r),
+    };
+    assert_eq!(gw.dst_as_path[0].kind, AsPathSegmentKind::AsSequence);
+    assert_eq!(gw.dst_as_path[0].asns, vec![65001, 200000]);
+    assert_eq!(gw.dst_as_path[1].kind, AsPathSegmentKind::AsSet);
+    assert_eq!(gw.neighbor_as(), Some(65001));
+    assert_eq!(gw.origin_as(), None);
+    assert_eq!(gw.path_length(), 3);
+
+    assert_eq!(gw.communities[0].to_string(), "65000:666");
+    assert_eq!(gw.communities[0].well_known(), None);
+    assert_eq!(gw.well_known_communities(), vec![WellKnownCommunity::NoExport]);
+    assert!(gw.has_well_known_community(WellKnownCommunity::NoExport));
+    assert!(!gw.has_well_known_community(WellKnownCommunity::Blackhole));
+    assert_eq!(gw.localpref, 100);
+}
+
+#[test]
+fn test_decode_raw_sample_and_record() {
+    let raw_test_data = concat!("00000002", // sample count
+                                "00009005", // format: enterprise 9, format 5
+                                "00000008", // sample length
+                                "deadbeef",
+                                "cafef00d",
+                                "00000001", // format: flow sample
+                                "0000002c", // sample length
+                                "00000001", // sequence number
+                                "00000001", // source id
+                                "00000001", // sampling rate
+                                "00000001", // sample pool
+                                "00000000", // drops
+                                "00000001", // input
+                                "00000002", // output
+                                "00000001", // flow record count
+                                "0000044b", // format 1099 is not decoded
+                                "00000004",
+                                "01020304");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let samples: Vec<SampleRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(samples.len(), 2);
+    match samples[0] {
+        SampleRecord::RawSample(ref rs) => {
+            assert_eq!(rs.enterprise, 9);
+            assert_eq!(rs.format, 5);
+            assert_eq!(rs.data, vec![0xde, 0xad, 0xbe, 0xef, 0xca, 0xfe, 0xf0, 0x0d]);
+        }
+        ref s => panic!("expected raw sample, got {:?}", s),
+    }
+    match samples[1] {
+        SampleRecord::FlowSample(ref fs) => {
+            match fs.flow_records[0] {
+                FlowRecord::RawRecord(ref r) => {
+                    assert_eq!(r.enterprise, 0);
+                    assert_eq!(r.format, 1099);
+                    assert_eq!(r.data, vec![1, 2, 3, 4]);
+                }
+                ref r => panic!("expected raw record, got {:?}", r),
+            }
+        }
+        ref s => panic!("expected flow sample, got {:?}", s),
+    }
+}
+
+add_decoder!{
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestVendorRecord {
+    pub a: u32,
+    pub b: u16,
+}
+}
+
+#[test]
+fn test_decode_registered_records() {
+    register_flow_record::<TestVendorRecord>(54321, 7);
+    register_counter_record::<TestVendorRecord>(54321, 8);
+
+    let raw_test_data = concat!("00000003", // flow record count
+                                "0d431007", // format: enterprise 54321, format 7
+                                "00000008",
+                                "0000002a",
+                                "00070000",
+                                "0d431008", // counter record format is not used for flows
+                                "00000004",
+                                "00000001",
+                                "0d431007", // too short for the registered decoder
+                                "00000004",
+                                "0000002a");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    match records[0] {
+        FlowRecord::Custom(ref c) => {
+            assert_eq!(c.enterprise, 54321);
+            assert_eq!(c.format, 7);
+            assert_eq!(c.downcast_ref::<TestVendorRecord>(),
+                       Some(&TestVendorRecord { a: 42, b: 7 }));
+            assert_eq!(c.downcast_ref::<u32>(), None);
+        }
+        ref r => panic!("expected custom record, got {:?}", r),
+    }
+    match records[1] {
+        FlowRecord::RawRecord(ref r) => assert_eq!((r.enterprise, r.format), (54321, 8)),
+        ref r => panic!("expected raw record, got {:?}", r),
+    }
+    match records[2] {
+        FlowRecord::RawRecord(ref r) => {
+            assert_eq!((r.enterprise, r.format), (54321, 7));
+            assert_eq!(r.data, vec![0, 0, 0, 0x2a]);
+        }
+        ref r => panic!("expected raw record, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_broadcom_counters() {
+    let raw_test_data = concat!("00000001", // counter record count
+                                // Broadcom port buffers
+                                "0113d002",
+                                "00000024",
+                                "000003e8", // ingress_uc_pc
+                                "00000000",
+                                "000007d0", // egress_uc_pc
+                                "00000000",
+                                "00000002", // egress queue count
+                                "000003e8",
+                                "00000bb8",
+                                "00000001", // egress queue count
+                                "00000064");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<CounterRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    match records[0] {
+        CounterRecord::BstPortBuffers(ref e) => {
+            assert_eq!(e.ingress_uc_pc, 1000);
+            assert_eq!(e.egress_uc_pc, 2000);
+            assert_eq!(e.egress_queue_uc_pc, vec![1000, 3000]);
+            assert_eq!(e.egress_queue_mc_pc, vec![100]);
+        }
+        ref r => panic!("expected broadcom port buffers, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_environmental_counters() {
+    let raw_test_data = concat!("00000002", // counter record count
+                                // Energy
+                                "00000bb8",
+                                "00000018",
+                                "0003a980", // voltage, 240000 mV
+                                "00002710", // current, 10000 mA
+                                "00225510", // real_power, 2250000 mW
+                                "0000005f", // power_factor, 95%
+                                "0036ee80", // energy, 3600000 mJ
+                                "00000000", // errors
+                                // Temperature
+                                "00000bb9",
+                                "0000000c",
+                                "fffffff6", // minimum, -10 C
+                                "00000028", // maximum, 40 C
+                                "00000001"); // errors
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<CounterRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    match records[0] {
+        CounterRecord::Energy(ref e) => {
+            assert_eq!(e.voltage_v(), 240.0);
+            assert_eq!(e.current_a(), 10.0);
+            assert_eq!(e.real_power_w(), 2250.0);
+            assert_eq!(e.power_factor_ratio(), 0.95);
+            assert_eq!(e.energy_j(), 3600.0);
+            assert_eq!(e.energy_kwh(), 0.001);
+        }
+        ref r => panic!("expected energy, got {:?}", r),
+    }
+
+    match records[1] {
+        CounterRecord::Temperature(ref t) => {
+            assert_eq!(t.minimum, -10);
+            assert_eq!(t.maximum, 40);
+            assert_eq!(t.minimum_f(), 14.0);
+            assert_eq!(t.maximum_f(), 104.0);
+            assert_eq!(t.errors, 1);
+        }
+        ref r => panic!("expected temperature, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_dissect_sampled_header() {
+    let raw_header = concat!("0a0b0c0d0e0f", // dst mac
+                             "010203040506", // src mac
+                             "8100", // 802.1Q
+                             "a064", // pcp 5, vid 100
+                             "0800", // IPv4
+                             "45000028", // version, ihl, total length
+                             "12344000", // identification, don't fragment
+                             "4006abcd", // ttl, protocol tcp, checksum
+                             "0a000001", // src
+                             "0a000002", // dst
+                             "c0000050", // src port 49152, dst port 80
+                             "00000001", // sequence number
+                             "00000000", // ack number
+                             "50020faf", // data offset, SYN, window
+                             "00000000"); // checksum, urgent pointer
+
+    let header = raw_header.from_hex().unwrap();
+    let sh = SampledHeader {
+        protocol: HeaderProtocol::EthernetIso88023,
+        frame_length: 64,
+        stripped: 4,
+        header: header.clone(),
+    };
+
+    let d = sh.dissect();
+    assert!(!d.truncated);
+    assert_eq!(d.layers.len(), 4);
+    assert_eq!(d.payload_offset, header.len());
+
+    match d.layers[0] {
+        Layer::Ethernet(ref e) => {
+            assert_eq!(e.src, MACAddress([1, 2, 3, 4, 5, 6]));
+            assert_eq!(e.ethertype, 0x8100);
+        }
+        ref l => panic!("expected ethernet, got {:?}", l),
+    }
+    match d.layers[1] {
+        Layer::Vlan(ref v) => {
+            assert_eq!(v.pcp, 5);
+            assert_eq!(v.vid, 100);
+        }
+        ref l => panic!("expected vlan, got {:?}", l),
+    }
+    match d.layers[2] {
+        Layer::Ipv4(ref ip) => {
+            assert_eq!(ip.src, Ipv4Addr::new(10, 0, 0, 1));
+            assert_eq!(ip.dst, Ipv4Addr::new(10, 0, 0, 2));
+            assert_eq!(ip.protocol, 6);
+        }
+        ref l => panic!("expected ipv4, got {:?}", l),
+    }
+    match d.layers[3] {
+        Layer::Tcp(ref t) => {
+            assert_eq!(t.src_port, 49152);
+            assert_eq!(t.dst_port, 80);
+            assert_eq!(t.flags, 0x002);
+        }
+        ref l => panic!("expected tcp, got {:?}", l),
+    }
+
+    // Formats that cannot be dissected give no layers.
+    let other = SampledHeader { protocol: HeaderProtocol::from_code(99), ..sh.clone() };
+    assert_eq!(other.protocol, HeaderProtocol::Other(99));
+    assert!(other.dissect().layers.is_empty());
+
+    // Every truncation keeps the layers that fit.
+    let boundaries = [14, 18, 38, 58];
+    for n in 0..header.len() {
+        let short = SampledHeader { header: header[..n].to_vec(), ..sh.clone() };
+        let d = short.dissect();
+        assert!(d.truncated);
+        assert_eq!(d.layers.len(), boundaries.iter().filter(|&&b| b <= n).count());
+        assert!(d.payload_offset <= n);
+    }
+}
+
+#[test]
+fn test_dissect_tcp_options() {
+    let raw_header = concat!("45000038", // version, ihl, total length
+                             "00004000", // identification, don't fragment
+                             "40060000", // ttl, protocol tcp, checksum
+                             "c0a80001", // src
+                             "c0a80002", // dst
+                             "01bbc000", // src port 443, dst port 49152
+                             "00000001", // sequence number
+                             "00000002", // ack number
+                             "80100100", // data offset 32, ACK, window
+                             "00000000", // checksum, urgent pointer
+                             "0101080a", "00000001", "00000002", // nop, nop, timestamps
+                             "deadbeef"); // payload
+
+    let header = raw_header.from_hex().unwrap();
+    let sh = SampledHeader {
+        protocol: HeaderProtocol::Ipv4,
+        frame_length: 70,
+        stripped: 4,
+        header: header.clone(),
+    };
+
+    let d = sh.dissect();
+    assert!(!d.truncated);
+    assert_eq!(d.layers.len(), 2);
+    assert_eq!(d.payload_offset, 52);
+    match d.layers[1] {
+        Layer::Tcp(ref t) => {
+            assert_eq!(t.src_port, 443);
+            assert_eq!(t.data_offset, 32);
+            assert_eq!(t.flags, 0x010);
+        }
+        ref l => panic!("expected tcp, got {:?}", l),
+    }
+
+    // Cutting the header anywhere before the end of the options is a truncation, even once the
+    // fixed TCP header is complete.
+    for n in 0..header.len() {
+        let short = SampledHeader { header: header[..n].to_vec(), ..sh.clone() };
+        let d = short.dissect();
+        assert_eq!(d.truncated, n < 52, "truncated at {}", n);
+        if n >= 40 {
+            assert_eq!(d.layers.len(), 2);
+        }
+    }
+}
+
+#[test]
+fn test_dissect_mpls_ipv6() {
+    let raw_header = concat!("00064040", // label 100, not bottom of stack
+                             "000c8140", // label 200, bottom of stack, ttl 64
+                             "60000000", // version, traffic class, flow label
+                             "00081140", // payload length, next header udp, hop limit
+                             "20010db8000000000000000000000001", // src
+                             "20010db8000000000000000000000002", // dst
+                             "30390035", // src port 12345, dst port 53
+                             "00080000"); // length, checksum
+
+    let sh = SampledHeader {
+        protocol: HeaderProtocol::Mpls,
+        frame_length: 100,
+        stripped: 4,
+        header: raw_header.from_hex().unwrap(),
+    };
+
+    let d = sh.dissect();
+    assert!(!d.truncated);
+    match d.layers[0] {
+        Layer::Mpls(ref m) => {
+            assert_eq!(m.label, 100);
+            assert!(!m.bottom_of_stack);
+        }
+        ref l => panic!("expected mpls, got {:?}", l),
+    }
+    match d.layers[1] {
+        Layer::Mpls(ref m) => {
+            assert_eq!(m.label, 200);
+            assert!(m.bottom_of_stack);
+            assert_eq!(m.ttl, 64);
+        }
+        ref l => panic!("expected mpls, got {:?}", l),
+    }
+    match d.layers[2] {
+        Layer::Ipv6(ref ip) => assert_eq!(ip.dst, "2001:db8::2".parse::<Ipv6Addr>().unwrap()),
+        ref l => panic!("expected ipv6, got {:?}", l),
+    }
+    match d.layers[3] {
+        Layer::Udp(ref u) => assert_eq!(u.dst_port, 53),
+        ref l => panic!("expected udp, got {:?}", l),
+    }
+}
+
+#[test]
+fn test_decode_record_length_mismatch() {
+    let raw_test_data = concat!("00000002", // counter record count
+                                // Processor, 4 bytes longer than the structure
+                                "000003e9",
+                                "00000020",
+                                "00000064", // cpu_5s
+                                "000000c8", // cpu_1m
+                                "0000012c", // cpu_5m
+                                "0000000100000000", // total_memory
+                                "0000000080000000", // free_memory
+                                "deadbeef", // trailing bytes
+                                // Processor
+                                "000003e9",
+                                "0000001c",
+                                "00000001", // cpu_5s
+                                "00000002", // cpu_1m
+                                "00000003", // cpu_5m
+                                "0000000000000400", // total_memory
+                                "0000000000000200"); // free_memory
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<CounterRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(records.len(), 2);
+    match records[0] {
+        CounterRecord::Processor(ref p) => {
+            assert_eq!(p.cpu_5m, 300);
+            assert_eq!(p.free_memory, 0x80000000);
+        }
+        ref r => panic!("expected processor, got {:?}", r),
+    }
+    match records[1] {
+        CounterRecord::Processor(ref p) => {
+            assert_eq!(p.cpu_5s, 1);
+            assert_eq!(p.total_memory, 1024);
+            assert_eq!(p.free_memory, 512);
+        }
+        ref r => panic!("expected processor, got {:?}", r),
+    }
+
+    // A record shorter than its structure is rejected rather than read into the next one.
+    let raw_test_data = concat!("00000002", // counter record count
+                                "000003e9",
+                                "00000008",
+                                "00000064", // cpu_5s
+                                "000000c8", // cpu_1m
+                                "000003e9",
+                                "0000001c");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let result: Result<Vec<CounterRecord>, Error> =
+        ::utils::Decodeable::read_and_decode(&mut data);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_decode_oversized_record_length() {
+    let raw_test_data = concat!("00000001", // flow record count
+                                "0000044b",
+                                "fffffff0", // length, far more than the datagram holds
+                                "00000000",
+                                "00000000");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let result: Result<Vec<FlowRecord>, Error> = ::utils::Decodeable::read_and_decode(&mut data);
+
+    match result {
+        Err(Error::Io(ref e)) => assert_eq!(e.kind(), ::std::io::ErrorKind::UnexpectedEof),
+        r => panic!("expected unexpected eof, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_extended_user_nat_and_mpls_records() {
+    let raw_test_data = concat!("00000006", // flow record count
+                                // Extended user
+                                "000003ec",
+                                "0000001c",
+                                "0000006a", // src_charset: UTF-8
+                                "00000005", "616c696365000000", // src_user
+                                "0000006a", // dst_charset: UTF-8
+                                "00000003", "626f6200", // dst_user
+                                // Extended nat
+                                "000003ef",
+                                "0000001c",
+                                "00000001", "c0a80001", // src_address
+                                "00000002", "20010db8000000000000000000000001", // dst_address
+                                // Extended mpls vc
+                                "000003f1",
+                                "00000010",
+                                "00000003", "76633100", // vc_instance_name
+                                "00000064", // vll_vc_id
+                                "00000005", // vc_label_cos
+                                // Extended mpls ftn
+                                "000003f2",
+                                "0000000c",
+                                "00000004", "66746e30", // mpls_ftn_descr
+                                "00000018", // mpls_ftn_mask
+                                // Extended mpls ldp fec
+                                "000003f3",
+                                "00000004",
+                                "00000020", // mpls_fec_addr_prefix_length
+                                // Extended vlan tunnel
+                                "000003f4",
+                                "0000000c",
+                                "00000002", // stack count
+                                "88a80064",
+                                "81000065");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(records.len(), 6);
+    match records[0] {
+        FlowRecord::ExtendedUser(ref e) => {
+            assert_eq!(e.src_charset, 106);
+            assert_eq!(e.src_user, b"alice".to_vec());
+            assert_eq!(e.dst_user, b"bob".to_vec());
+        }
+        ref r => panic!("expected extended user, got {:?}", r),
+    }
+    match records[1] {
+        FlowRecord::ExtendedNat(ref e) => {
+            match e.src_address {
+                IPAddress::IPv4(a) => assert_eq!(a, Ipv4Addr::new(192, 168, 0, 1)),
+                ref a => panic!("expected ipv4 address, got {:?}", a),
+            }
+            match e.dst_address {
+                IPAddress::IPv6(a) => assert_eq!(a, "2001:db8::1".parse::<Ipv6Addr>().unwrap()),
+                ref a => panic!("expected ipv6 address, got {:?}", a),
+            }
+        }
+        ref r => panic!("expected extended nat, got {:?}", r),
+    }
+    match records[2] {
+        FlowRecord::ExtendedMplsVc(ref e) => {
+            assert_eq!(e.vc_instance_name, "vc1");
+            assert_eq!(e.vll_vc_id, 100);
+            assert_eq!(e.vc_label_cos, 5);
+        }
+        ref r => panic!("expected extended mpls vc, got {:?}", r),
+    }
+    match records[3] {
+        FlowRecord::ExtendedMplsFtn(ref e) => {
+            assert_eq!(e.mpls_ftn_descr, "ftn0");
+            assert_eq!(e.mpls_ftn_mask, 24);
+        }
+        ref r => panic!("expected extended mpls ftn, got {:?}", r),
+    }
+    match records[4] {
+        FlowRecord::ExtendedMplsLdpFec(ref e) => assert_eq!(e.mpls_fec_addr_prefix_length, 32),
+        ref r => panic!("expected extended mpls ldp fec, got {:?}", r),
+    }
+    match records[5] {
+        FlowRecord::ExtendedVlanTunnel(ref e) => assert_eq!(e.stack, vec![0x88a80064, 0x81000065]),
+        ref r => panic!("expected extended vlan tunnel, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_v4_extended_user() {
+    let raw_test_data = concat!("00000004", // version
+                                "00000001", // agent address type
+                                "0a000001", // agent address
+                                "00000001", // sequence number
+                                "00001000", // uptime
+                                "00000001", // sample count
+                                // Flow sample
+                                "00000001",
+                                "00000001", // sequence number
+                                "00000002", // source id
+                                "00000100", // sampling rate
+                                "00001000", // sample pool
+                                "00000000", // drops
+                                "00000002", // input
+                                "00000003", // output
+                                "00000001", // packet data: header
+                                "00000001", // protocol
+                                "00000040", // frame length
+                                "00000000", // header length
+                                "00000001", // extended data count
+                                "00000004", // extended user
+                                "00000005", "616c696365000000", // src_user
+                                "00000003", "ff62fe00"); // dst_user, not UTF-8
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let d: Datagram = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    match d.sample_record[0] {
+        SampleRecord::FlowSample(ref fs) => {
+            match fs.flow_records[1] {
+                FlowRecord::ExtendedUser(ref e) => {
+                    assert_eq!(e.src_charset, 0);
+                    assert_eq!(e.src_user, b"alice".to_vec());
+                    assert_eq!(e.dst_charset, 0);
+                    assert_eq!(e.dst_user, vec![0xff, 0x62, 0xfe]);
+                }
+                ref r => panic!("expected extended user, got {:?}", r),
+            }
+        }
+        ref s => panic!("expected flow sample, got {:?}", s),
+    }
+    assert_eq!(data.position(), data.get_ref().len() as u64);
+}
+
+#[test]
+fn test_decode_jvm_counters() {
+    let raw_test_data = concat!("00000002", // counter record count
+                                // Jvm runtime
+                                "00000839",
+                                "00000040",
+                                "00000018", // vm_name
+                                "4f70656e4a444b2036342d4269742053657276657220564d",
+                                "00000012", // vm_vendor
+                                "4f7261636c6520436f72706f726174696f6e0000",
+                                "00000006", "31372e302e320000", // vm_version
+                                // Jmx
+                                "0000083a",
+                                "0000006c",
+                                "0000000010000000", // heap_initial
+                                "0000000123456789", // heap_used
+                                "0000000200000000", // heap_committed
+                                "0000000400000000", // heap_max
+                                "0000000000100000", // non_heap_initial
+                                "0000000003000000", // non_heap_used
+                                "0000000004000000", // non_heap_committed
+                                "ffffffffffffffff", // non_heap_max
+                                "00000010", // gc_count
+                                "000000fa", // gc_time
+                                "00001388", // classes_loaded
+                                "00001400", // classes_total
+                                "00000078", // classes_unloaded
+                                "000007d0", // compilation_time
+                                "00000020", // thread_num_live
+                                "00000008", // thread_num_daemon
+                                "00000040", // thread_num_started
+                                "00000080", // fd_open_count
+                                "00010000"); // fd_max_count
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<CounterRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(records.len(), 2);
+    match records[0] {
+        CounterRecord::JvmRuntime(ref e) => {
+            assert_eq!(e.vm_name, "OpenJDK 64-Bit Server VM");
+            assert_eq!(e.vm_vendor, "Oracle Corporation");
+            assert_eq!(e.vm_version, "17.0.2");
+        }
+        ref r => panic!("expected jvm runtime, got {:?}", r),
+    }
+    match records[1] {
+        CounterRecord::Jmx(ref e) => {
+            assert_eq!(e.heap_used, 0x123456789);
+            assert_eq!(e.heap_max, 0x400000000);
+            assert_eq!(e.non_heap_max, 0xffffffffffffffff);
+            assert_eq!(e.gc_count, 16);
+            assert_eq!(e.thread_num_started, 64);
+            assert_eq!(e.fd_max_count, 65536);
+        }
+        ref r => panic!("expected jmx, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_transit_and_queue() {
+    let raw_test_data = concat!("00000002", // flow record count
+                                // Extended transit
+                                "0000040f",
+                                "00000004",
+                                "0001e240", // delay
+                                // Extended queue
+                                "00000410",
+                                "00000004",
+                                "00030d40"); // depth
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<FlowRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(records.len(), 2);
+    match records[0] {
+        FlowRecord::ExtendedTransit(ref e) => assert_eq!(e.delay, 123456),
+        ref r => panic!("expected extended transit, got {:?}", r),
+    }
+    match records[1] {
+        FlowRecord::ExtendedQueue(ref e) => assert_eq!(e.depth, 200000),
+        ref r => panic!("expected extended queue, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_interface_and_device_counters() {
+    let raw_test_data = concat!("00000006", // counter record count
+                                // Token ring
+                                "00000003",
+                                "00000048",
+                                "00000001", // dot5_stats_line_errors
+                                "00000002",
+                                "00000003",
+                                "00000004",
+                                "00000005",
+                                "00000006",
+                                "00000007",
+                                "00000008",
+                                "00000009",
+                                "0000000a",
+                                "0000000b",
+                                "0000000c",
+                                "0000000d",
+                                "0000000e",
+                                "0000000f",
+                                "00000010",
+                                "00000011",
+                                "00000012", // dot5_stats_freq_errors
+                                // 100BaseVG
+                                "00000004",
+                                "00000050",
+                                "0000000a", // dot12_in_high_priority_frames
+                                "0000000100000001", // dot12_in_high_priority_octets
+                                "00000014",
+                                "0000000200000002", // dot12_in_norm_priority_octets
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "0000001e",
+                                "0000000300000003",
+                                "00000004", // dot12_transition_into_trainings
+                                "0000000400000004",
+                                "0000000500000005",
+                                "0000000600000006", // dot12_hc_out_high_priority_octets
+                                // Vlan
+                                "00000005",
+                                "0000001c",
+                                "00000064", // vlan_id
+                                "0000001234567890", // octets
+                                "00000007", // ucast_pkts
+                                "00000008", // multicast_pkts
+                                "00000009", // broadcast_pkts
+                                "0000000a", // discards
+                                // Processor
+                                "000003e9",
+                                "0000001c",
+                                "000005dc", // cpu_5s
+                                "000009c4", // cpu_1m
+                                "00000dac", // cpu_5m
+                                "0000000400000000", // total_memory
+                                "0000000100000000", // free_memory
+                                // OpenFlow port
+                                "000003ec",
+                                "0000000c",
+                                "0000123456789abc", // datapath_id
+                                "00000003", // port_no
+                                // Port name
+                                "000003ed",
+                                "00000008",
+                                "00000004", "65746830"); // name
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let records: Vec<CounterRecord> = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(records.len(), 6);
+    match records[0] {
+        CounterRecord::TokenRing(ref e) => {
+            assert_eq!(e.dot5_stats_line_errors, 1);
+            assert_eq!(e.dot5_stats_freq_errors, 18);
+        }
+        ref r => panic!("expected token ring, got {:?}", r),
+    }
+    match records[1] {
+        CounterRecord::Vg(ref e) => {
+            assert_eq!(e.dot12_in_high_priority_frames, 10);
+            assert_eq!(e.dot12_in_high_priority_octets, 0x100000001);
+            assert_eq!(e.dot12_in_norm_priority_octets, 0x200000002);
+            assert_eq!(e.dot12_out_high_priority_octets, 0x300000003);
+            assert_eq!(e.dot12_transition_into_trainings, 4);
+            assert_eq!(e.dot12_hc_in_high_priority_octets, 0x400000004);
+            assert_eq!(e.dot12_hc_out_high_priority_octets, 0x600000006);
+        }
+        ref r => panic!("expected 100BaseVG, got {:?}", r),
+    }
+    match records[2] {
+        CounterRecord::Vlan(ref e) => {
+            assert_eq!(e.vlan_id, 100);
+            assert_eq!(e.octets, 0x1234567890);
+            assert_eq!(e.ucast_pkts, 7);
+            assert_eq!(e.discards, 10);
+        }
+        ref r => panic!("expected vlan, got {:?}", r),
+    }
+    match records[3] {
+        CounterRecord::Processor(ref e) => {
+            assert_eq!(e.cpu_5s, 1500);
+            assert_eq!(e.cpu_5m, 3500);
+            assert_eq!(e.total_memory, 0x400000000);
+            assert_eq!(e.free_memory, 0x100000000);
+        }
+        ref r => panic!("expected processor, got {:?}", r),
+    }
+    match records[4] {
+        CounterRecord::OfPort(ref e) => {
+            assert_eq!(e.datapath_id, 0x123456789abc);
+            assert_eq!(e.port_no, 3);
+        }
+        ref r => panic!("expected openflow port, got {:?}", r),
+    }
+    match records[5] {
+        CounterRecord::PortName(ref e) => assert_eq!(e.name, "eth0"),
+        ref r => panic!("expected port name, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_v2_counter_samples() {
+    let raw_test_data = concat!("00000002", // version
+                                "00000001", // agent address type
+                                "0a000001", // agent address
+                                "00000007", // sequence number
+                                "00001000", // uptime
+                                "00000003", // sample count
+                                // Counter sample, token ring
+                                "00000002",
+                                "00000001", // sequence number
+                                "00000002", // source id
+                                "0000001e", // sampling interval
+                                "00000003", // counters type: token ring
+                                "00000002", // if_index
+                                "00000006",
+                                "0000000005f5e100",
+                                "00000001",
+                                "00000003",
+                                "0000000000000001",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "0000000000000002",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000001", // dot5_stats_line_errors
+                                "00000002",
+                                "00000003",
+                                "00000004",
+                                "00000005",
+                                "00000006",
+                                "00000007",
+                                "00000008",
+                                "00000009",
+                                "0000000a",
+                                "0000000b",
+                                "0000000c",
+                                "0000000d",
+                                "0000000e",
+                                "0000000f",
+                                "00000010",
+                                "00000011",
+                                "00000012", // dot5_stats_freq_errors
+                                // Counter sample, 100BaseVG
+                                "00000002",
+                                "00000002", // sequence number
+                                "00000002", // source id
+                                "0000001e", // sampling interval
+                                "00000005", // counters type: 100BaseVG
+                                "00000002", // if_index
+                                "00000006",
+                                "0000000005f5e100",
+                                "00000001",
+                                "00000003",
+                                "0000000000000001",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "0000000000000002",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "0000000a", // dot12_in_high_priority_frames
+                                "0000000100000001", // dot12_in_high_priority_octets
+                                "00000014",
+                                "0000000200000002", // dot12_in_norm_priority_octets
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "00000000",
+                                "0000001e",
+                                "0000000300000003",
+                                "00000004", // dot12_transition_into_trainings
+                                "0000000400000004",
+                                "0000000500000005",
+                                "0000000600000006", // dot12_hc_out_high_priority_octets
+                                // Counter sample, vlan
+                                "00000002",
+                                "00000003", // sequence number
+                                "00000002", // source id
+                                "0000001e", // sampling interval
+                                "00000007", // counters type: vlan
+                                "00000064", // vlan_id
+                                "0000001234567890", // octets
+                                "00000007", // ucast_pkts
+                                "00000008", // multicast_pkts
+                                "00000009", // broadcast_pkts
+                                "0000000a"); // discards
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let d: Datagram = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    assert_eq!(d.sflow_version, 2);
+    assert_eq!(d.sample_record.len(), 3);
+    assert_eq!(data.position(), data.get_ref().len() as u64);
+
+    let records: Vec<&Vec<CounterRecord>> = d.sample_record
+        .iter()
+        .map(|s| match *s {
+            SampleRecord::CounterSample(ref cs) => &cs.counter_records,
+            ref s => panic!("expected counter sample, got {:?}", s),
+        })
+        .collect();
+
+    // Token ring and 100BaseVG counters follow the generic interface counters, VLAN counters
+    // stand alone.
+    assert_eq!(records[0].len(), 2);
+    match records[0][1] {
+        CounterRecord::TokenRing(ref e) => assert_eq!(e.dot5_stats_freq_errors, 18),
+        ref r => panic!("expected token ring, got {:?}", r),
+    }
+    assert_eq!(records[1].len(), 2);
+    match records[1][1] {
+        CounterRecord::Vg(ref e) => {
+            assert_eq!(e.dot12_in_high_priority_octets, 0x100000001);
+            assert_eq!(e.dot12_hc_out_high_priority_octets, 0x600000006);
+        }
+        ref r => panic!("expected 100BaseVG, got {:?}", r),
+    }
+    assert_eq!(records[2].len(), 1);
+    match records[2][0] {
+        CounterRecord::Vlan(ref e) => {
+            assert_eq!(e.vlan_id, 100);
+            assert_eq!(e.octets, 0x1234567890);
+            assert_eq!(e.discards, 10);
+        }
+        ref r => panic!("expected vlan, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_nvidia_gpu() {
+    let raw_test_data = concat!("01647001", // format: enterprise 5703, format 1
+                                "00000030",
+                                "00000004", // device_count
+                                "0000000c", // processes
+                                "0001d4c0", // gpu_time
+                                "0000ea60", // mem_time
+                                "0000000a00000000", // mem_total
+                                "0000000280000000", // mem_free
+                                "00000001", // ecc_errors
+                                "05f5e100", // energy
+                                "0000004b", // temperature
+                                "00000037"); // fan_speed
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let record: CounterRecord = ::utils::Decodeable::read_and_decode(&mut data).unwrap();
+
+    match record {
+        CounterRecord::NvidiaGpu(ref e) => {
+            assert_eq!(e.device_count, 4);
+            assert_eq!(e.processes, 12);
+            assert_eq!(e.gpu_time, 120000);
+            assert_eq!(e.mem_time, 60000);
+            assert_eq!(e.mem_total, 40 << 30);
+            assert_eq!(e.mem_free, 10 << 30);
+            assert_eq!(e.ecc_errors, 1);
+            assert_eq!(e.energy, 100000000);
+            assert_eq!(e.temperature, 75);
+            assert_eq!(e.fan_speed, 55);
+        }
+        ref r => panic!("expected nvidia gpu, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_decode_truncated_mac_address() {
+    let raw_test_data = concat!("00000001", // counter record count
+                                // Host adapters, missing the padding of the last address
+                                "000007d1",
+                                "00000012",
+                                "00000001", // adapter count
+                                "00000002", // if_index
+                                "00000001", // mac address count
+                                "525400123456");
+
+    let mut data = Cursor::new(raw_test_data.from_hex().unwrap());
+    let result: Result<Vec<CounterRecord>, Error> =
+        ::utils::Decodeable::read_and_decode(&mut data);
+    assert!(result.is_err());
+}
@@ -6,5 +6,32 @@ use std::io::{Read, Seek};
 pub type Interface = u32;
 pub type SourceID = u32;
 
+/// DataSource is a SourceID split into its type and index. The type is 0 for an ifIndex, 1 for a
+/// smonVlanDataSource, 2 for an entPhysicalEntry and 3 for a logical entity such as a virtual
+/// machine, which is how virtual host counters identify the machine they describe.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DataSource {
+    pub source_id_type: u32,
+    pub source_id_index: u32,
+}
+
+impl DataSource {
+    /// from_source_id splits a compact SourceID, where the type is the top 8 bits and the index
+    /// is the bottom 24 bits.
+    pub fn from_source_id(source_id: SourceID) -> DataSource {
+        DataSource {
+            source_id_type: source_id >> 24,
+            source_id_index: source_id & 0x00ff_ffff,
+        }
+    }
+}
+
+/// split_data_format splits an sFlow data format into its 20 bit enterprise and 12 bit format.
+/// Enterprise 0 is reserved for the structures defined by sflow.org, vendors use their SMI
+/// Private Enterprise Number.
+pub fn split_data_format(data_format: u32) -> (u32, u32) {
+    (data_format >> 12, data_format & 0xfff)
+}
+
 pub trait ReadSeeker: Read + Seek {}
 impl<T> ReadSeeker for T where T: Read + Seek {}
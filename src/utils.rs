@@ -2,8 +2,7 @@ use byteorder::{self, ByteOrder, BigEndian, ReadBytesExt};
 use error;
 use types;
 
-use std::io::{self, SeekFrom, Read};
-use std::mem::size_of;
+use std::io::{self, Cursor, SeekFrom, Read};
 use std::vec::Vec;
 
 pub trait ReadBytesLocal: io::Read {
@@ -13,6 +12,18 @@ pub trait ReadBytesLocal: io::Read {
         return self.read_u32::<BigEndian>();
     }
 
+    #[inline]
+    /// be_read_u64 will read 64 bits in *b*ig *e*dian format.
+    fn be_read_u64(&mut self) -> Result<u64, byteorder::Error> {
+        return self.read_u64::<BigEndian>();
+    }
+
+    #[inline]
+    /// be_read_f32 will read a 32 bit float in *b*ig *e*dian format.
+    fn be_read_f32(&mut self) -> Result<f32, byteorder::Error> {
+        return self.read_f32::<BigEndian>();
+    }
+
     #[inline]
     /// be_read_u16 will read 16 bits in *b*ig *e*dian format.
     fn be_read_u16(&mut self) -> Result<u16, byteorder::Error> {
@@ -63,6 +74,48 @@ macro_rules! add_decoder {
     };
 }
 
+// The add_code_enum macro builds an enum for a field that is a u32 code on the wire. Every code
+// listed gets its own variant, anything else is kept in an Other variant so the raw value is never
+// lost. Codes must be literals.
+macro_rules! add_code_enum {
+    ( $( #[$enum_attr:meta] )*
+    pub enum $name:ident {
+        $( $( #[$variant_attr:meta] )* $variant:ident = $code:tt, )*
+    }) => {
+        $( #[$enum_attr] )*
+        pub enum $name {
+            $( $( #[$variant_attr] )* $variant, )*
+            Other(u32),
+        }
+
+        impl $name {
+            /// from_code returns the variant for a raw code.
+            pub fn from_code(code: u32) -> $name {
+                match code {
+                    $( $code => $name::$variant, )*
+                    _ => $name::Other(code),
+                }
+            }
+
+            /// code returns the raw code as it appeared on the wire.
+            pub fn code(&self) -> u32 {
+                match *self {
+                    $( $name::$variant => $code, )*
+                    $name::Other(code) => code,
+                }
+            }
+        }
+
+        impl ::utils::Decodeable for $name {
+            fn read_and_decode(stream: &mut ::types::ReadSeeker) -> ::std::result::Result<$name, ::error::Error> {
+                let code: u32 = try!(::utils::Decodeable::read_and_decode(stream));
+
+                Ok($name::from_code(code))
+            }
+        }
+    };
+}
+
 pub trait Decodeable {
     fn read_and_decode(&mut types::ReadSeeker) -> Result<Self, ::error::Error> where Self: Sized;
 }
@@ -85,6 +138,15 @@ impl Decodeable for u32 {
     }
 }
 
+impl Decodeable for u64 {
+    #[inline]
+    fn read_and_decode(stream: &mut types::ReadSeeker) -> Result<u64, error::Error> {
+        let r = try!(stream.be_read_u64());
+
+        Ok(r)
+    }
+}
+
 impl Decodeable for u16 {
     #[inline]
     fn read_and_decode(stream: &mut types::ReadSeeker) -> Result<Self, error::Error> {
@@ -121,18 +183,31 @@ impl Decodeable for i16 {
     }
 }
 
+impl Decodeable for f32 {
+    #[inline]
+    fn read_and_decode(stream: &mut types::ReadSeeker) -> Result<f32, error::Error> {
+        let r = try!(stream.be_read_f32());
+
+        Ok(r)
+    }
+}
+
+// Fixed length opaque[16], used for UUIDs. Sixteen bytes never needs padding.
+impl Decodeable for [u8; 16] {
+    fn read_and_decode(stream: &mut types::ReadSeeker) -> Result<Self, error::Error> {
+        let mut buf: [u8; 16] = [0; 16];
+        try!(stream.read_exact(&mut buf));
+
+        Ok(buf)
+    }
+}
+
 impl Decodeable for String {
     fn read_and_decode(stream: &mut types::ReadSeeker) -> Result<Self, error::Error> {
         // Get the XDR length
-        let length: usize = try!(stream.be_read_u32()) as usize;
-
-        // Create a buffer to read the buf.
-        let mut buf: Vec<u8> = Vec::with_capacity(length);
-        unsafe {
-            buf.set_len(length);
-        }
+        let length = try!(stream.be_read_u32());
 
-        try!(stream.read_exact(&mut buf));
+        let buf = try!(read_data(stream, length));
         let s = try!(String::from_utf8(buf));
 
         // We need to figure out how much padding will be needed.
@@ -148,33 +223,59 @@ impl Decodeable for String {
     }
 }
 
-impl<T: Decodeable> Decodeable for Vec<T> {
-    fn read_and_decode(stream: &mut types::ReadSeeker) -> Result<Vec<T>, error::Error> {
-        // First we need to figure out how many samples there are.
-        let count = try!(stream.be_read_u32());
-        let mut results: Vec<T> = Vec::new();
-
-        // We need to figure out how much padding will be needed.
-        let total_size = ((count as usize) * size_of::<T>()) as i64;
+/// read_data reads length bytes of opaque data, such as the body of a record. The length comes
+/// off the wire, so the buffer only grows as bytes are actually read rather than being allocated
+/// up front.
+pub fn read_data(stream: &mut types::ReadSeeker, length: u32) -> Result<Vec<u8>, error::Error> {
+    let mut data: Vec<u8> = Vec::new();
+    try!(Read::take(&mut *stream, length as u64).read_to_end(&mut data));
 
-        let mut padding = (4 - total_size) % 4;
-        if padding < 0 {
-            padding += 4
-        }
+    if data.len() != length as usize {
+        let err_string = format!("expected {} bytes of data, found {}", length, data.len());
+        return Err(error::Error::Io(io::Error::new(io::ErrorKind::UnexpectedEof, err_string)));
+    }
 
-        for _ in 0..count {
-            let x: Result<T, error::Error> = ::utils::Decodeable::read_and_decode(stream);
+    Ok(data)
+}
 
-            match x {
-                Ok(x) => results.push(x),
-                Err(error::Error::UnknownType(_)) => continue,
-                Err(e) => return Err(e),
-            }
+/// read_body reads the body of a sample or record so it can be decoded from its own bytes. A body
+/// longer than the structure it holds then cannot misalign whatever follows it: trailing bytes
+/// are skipped, and a body that is too short is an error.
+pub fn read_body(stream: &mut types::ReadSeeker,
+                 length: u32)
+                 -> Result<Cursor<Vec<u8>>, error::Error> {
+    let data = try!(read_data(stream, length));
 
-        }
+    Ok(Cursor::new(data))
+}
 
-        try!(stream.seek(SeekFrom::Current(padding as i64)));
+impl<T: Decodeable> Decodeable for Vec<T> {
+    fn read_and_decode(stream: &mut types::ReadSeeker) -> Result<Vec<T>, error::Error> {
+        read_vec_with(stream, T::read_and_decode)
+    }
+}
 
-        Ok(results)
+/// read_vec_with reads a counted list whose elements are decoded by decode, for elements that need
+/// more context than Decodeable passes along.
+pub fn read_vec_with<T, F>(stream: &mut types::ReadSeeker,
+                           decode: F)
+                           -> Result<Vec<T>, error::Error>
+    where F: Fn(&mut types::ReadSeeker) -> Result<T, error::Error>
+{
+    // First we need to figure out how many samples there are.
+    let count = try!(stream.be_read_u32());
+    let mut results: Vec<T> = Vec::new();
+    let start = try!(stream.stream_position());
+
+    for _ in 0..count {
+        results.push(try!(decode(stream)));
     }
+
+    // We need to figure out how much padding will be needed. This depends on how many bytes
+    // were actually read, as the in memory size of T says nothing about its XDR size.
+    let end = try!(stream.stream_position());
+    let padding = (4 - (end - start) % 4) % 4;
+    try!(stream.seek(SeekFrom::Current(padding as i64)));
+
+    Ok(results)
 }
@@ -0,0 +1,126 @@
+//! Wireless holds the IEEE 802.11 flow and counter structures.
+
+// Local Imports
+use error;
+use flow_records::FlowRecord;
+use macaddress::MACAddress;
+use types::ReadSeeker;
+use utils::read_vec_with;
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct Extended80211Payload {
+   pub cipher_suite: u32, /* encryption scheme used for this packet, as an IEEE 802.11 cipher
+                             suite selector */
+   pub data: Vec<u8>,     /* unencrypted bytes from the payload */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct Extended80211Rx {
+   pub ssid: String,          /* SSID string */
+   pub bssid: MACAddress,     /* BSSID */
+   pub version: u32,          /* version: a = 1, b = 2, g = 3, n = 4 */
+   pub channel: u32,          /* channel number */
+   pub speed: u64,            /* speed in bits per second */
+   pub rsni: u32,             /* received signal to noise ratio, see dot11FrameRprtRSNI */
+   pub rcpi: u32,             /* received channel power, see dot11FrameRprtLastRCPI */
+   pub packet_duration: u32,  /* amount of time that the successfully received pkt occupied
+                                 RF medium, in microseconds */
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone)]
+pub struct Extended80211Tx {
+   pub ssid: String,          /* SSID string */
+   pub bssid: MACAddress,     /* BSSID */
+   pub version: u32,          /* version: a = 1, b = 2, g = 3, n = 4 */
+   pub transmissions: u32,    /* number of transmissions for sampled packet.
+                                 0 = unknown
+                                 1 = packet was successfully transmitted on first attempt
+                                 n > 1 = n - 1 retransmissions */
+   pub packet_duration: u32,  /* amount of time that the successfully transmitted packet
+                                 occupied the RF medium, in microseconds */
+   pub retrans_duration: u32, /* amount of time that failed transmission attempts occupied the
+                                 RF medium, in microseconds */
+   pub channel: u32,          /* channel number */
+   pub speed: u64,            /* speed in bits per second */
+   pub power: u32,            /* transmit power in mW */
+}
+}
+
+#[derive(Debug, Clone)]
+pub struct Extended80211Aggregation {
+   pub pdus: Vec<Pdu>, /* the PDUs making up the aggregated frame */
+}
+
+impl Extended80211Aggregation {
+    /// read_nested decodes an aggregation whose flow records sit depth aggregations deep.
+    pub(crate) fn read_nested(stream: &mut ReadSeeker, depth: u32)
+                              -> Result<Extended80211Aggregation, error::Error> {
+        let pdus = try!(read_vec_with(stream, |s| Pdu::read_nested(s, depth)));
+        Ok(Extended80211Aggregation { pdus })
+    }
+}
+
+impl ::utils::Decodeable for Extended80211Aggregation {
+    fn read_and_decode(stream: &mut ReadSeeker) -> Result<Extended80211Aggregation, error::Error> {
+        Extended80211Aggregation::read_nested(stream, 1)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Pdu {
+   pub flow_records: Vec<FlowRecord>, /* the flow records describing this PDU */
+}
+
+impl Pdu {
+    /// read_nested decodes a PDU whose flow records sit depth aggregations deep.
+    pub(crate) fn read_nested(stream: &mut ReadSeeker, depth: u32) -> Result<Pdu, error::Error> {
+        let flow_records = try!(read_vec_with(stream, |s| FlowRecord::read_nested(s, depth)));
+        Ok(Pdu { flow_records })
+    }
+}
+
+impl ::utils::Decodeable for Pdu {
+    fn read_and_decode(stream: &mut ReadSeeker) -> Result<Pdu, error::Error> {
+        Pdu::read_nested(stream, 1)
+    }
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct Ieee80211Counters {
+   pub dot11_transmitted_fragment_count: u32,
+   pub dot11_multicast_transmitted_frame_count: u32,
+   pub dot11_failed_count: u32,
+   pub dot11_retry_count: u32,
+   pub dot11_multiple_retry_count: u32,
+   pub dot11_frame_duplicate_count: u32,
+   pub dot11_rts_success_count: u32,
+   pub dot11_rts_failure_count: u32,
+   pub dot11_ack_failure_count: u32,
+   pub dot11_received_fragment_count: u32,
+   pub dot11_multicast_received_frame_count: u32,
+   pub dot11_fcs_error_count: u32,
+   pub dot11_transmitted_frame_count: u32,
+   pub dot11_wep_undecryptable_count: u32,
+   pub dot11_qos_discarded_fragment_count: u32,
+   pub dot11_associated_station_count: u32,
+   pub dot11_qos_cf_polls_received_count: u32,
+   pub dot11_qos_cf_polls_unused_count: u32,
+   pub dot11_qos_cf_polls_unusable_count: u32,
+   pub dot11_qos_cf_polls_lost_count: u32,
+}
+}
+
+add_decoder!{
+#[derive(Debug, Clone, Default)]
+pub struct RadioUtilization {
+   pub elapsed_time: u32,         /* elapsed time in ms */
+   pub on_channel_time: u32,      /* time in ms spent on channel */
+   pub on_channel_busy_time: u32, /* time in ms spent on channel and busy */
+}
+}